@@ -8,8 +8,13 @@ export class RemotePlayer extends Phaser.GameObjects.Sprite {
     }
 
     updateState(info) {
-        if (info.x !== undefined) this.x = info.x;
-        if (info.y !== undefined) this.y = info.y;
+        if (info.duration && info.x !== undefined && info.y !== undefined) {
+            this.scene.tweens.killTweensOf(this);
+            this.scene.tweens.add({ targets: this, x: info.x, y: info.y, duration: info.duration });
+        } else {
+            if (info.x !== undefined) this.x = info.x;
+            if (info.y !== undefined) this.y = info.y;
+        }
 
         if (info.anim) {
             console.log('RemotePlayer: Playing anim:', info.anim);
@@ -20,8 +20,11 @@ export default class MultiplayerScene extends Phaser.Scene {
             onCurrentPlayers: (players) => this.handleCurrentPlayers(players),
             onNewPlayer: (playerInfo) => this.handleNewPlayer(playerInfo),
             onPlayerMoved: (msg) => this.handlePlayerMoved(msg),
+            onSnapshot: (msg) => this.handleSnapshot(msg),
+            onCorrection: (msg) => this.handleCorrection(msg),
             onUserDisconnected: (id) => this.handleUserDisconnected(id),
-            onChat: (msg) => this.handleChat(msg)
+            onChat: (msg) => this.handleChat(msg),
+            onChatRejected: (reason) => this.chat.addMessageToLog('Server', reason)
         });
 
         this.otherPlayers = this.add.group();
@@ -106,6 +109,23 @@ export default class MultiplayerScene extends Phaser.Scene {
         }
     }
 
+    // The server rejected or shortened our last move
+    handleCorrection(msg) {
+        if (this.player && msg.scene === this.scene.key) {
+            this.player.body.reset(msg.x, msg.y);
+        }
+    }
+
+    // One batched update per server tick; remote players glide to their new
+    // positions over the time since the previous snapshot.
+    handleSnapshot(msg) {
+        const now = this.time.now;
+        const duration = this.lastSnapshotAt ? Phaser.Math.Clamp(now - this.lastSnapshotAt, 16, 250) : 0;
+        this.lastSnapshotAt = now;
+
+        msg.players.forEach((player) => this.handlePlayerMoved({ ...player, duration }));
+    }
+
     handleUserDisconnected(id) {
         if (this.otherPlayersMap.has(id)) {
             const otherPlayer = this.otherPlayersMap.get(id);
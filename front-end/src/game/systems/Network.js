@@ -1,15 +1,39 @@
+const SERVER_URL = 'ws://localhost:8000/ws';
+// Delay before reconnecting after the connection dropped, doubled on every
+// attempt that does not get a welcome
+const RECONNECT_DELAY_MS = 1000;
+const MAX_RECONNECT_DELAY_MS = 30000;
+// Close code the server uses for banned wallets
+const POLICY_VIOLATION = 1008;
+
 export class Network {
-    constructor(callbacks) {
+    // query: handshake parameters such as { token, protocol }, sent again on
+    // every reconnect alongside the resume token
+    constructor(callbacks, query = {}) {
         this.callbacks = callbacks;
+        this.query = query;
         this.socket = null;
         this.playerId = null;
+        // From the server's welcome; lets a reconnect resume this session
+        this.resumeToken = null;
+        this.reconnectAttempts = 0;
     }
 
     connect() {
         try {
-            this.socket = new WebSocket('ws://localhost:8000/ws');
+            const params = new URLSearchParams();
+            for (const [key, value] of Object.entries(this.query)) {
+                if (value !== undefined && value !== null) params.set(key, value);
+            }
+            if (this.resumeToken) params.set('resume', this.resumeToken);
+            const search = params.toString();
+            const url = search ? `${SERVER_URL}?${search}` : SERVER_URL;
+            const socket = new WebSocket(url);
+            this.socket = socket;
+            let opened = false;
 
             this.socket.onopen = () => {
+                opened = true;
                 console.log('Connected to server');
             };
 
@@ -19,6 +43,9 @@ export class Network {
                     switch (msg.type) {
                         case 'welcome':
                             this.playerId = msg.id;
+                            // A fresh welcome replaces the expired session's token
+                            this.resumeToken = msg.resumeToken || null;
+                            this.reconnectAttempts = 0;
                             if (this.callbacks.onWelcome) this.callbacks.onWelcome(msg.id);
                             break;
                         case 'currentPlayers':
@@ -30,20 +57,52 @@ export class Network {
                         case 'playerMoved':
                             if (this.callbacks.onPlayerMoved) this.callbacks.onPlayerMoved(msg);
                             break;
+                        case 'enterView':
+                            if (this.callbacks.onNewPlayer) this.callbacks.onNewPlayer(msg.player);
+                            break;
+                        case 'leaveView':
+                            if (this.callbacks.onUserDisconnected) this.callbacks.onUserDisconnected(msg.id);
+                            break;
+                        case 'snapshot':
+                            if (this.callbacks.onSnapshot) this.callbacks.onSnapshot(msg);
+                            break;
+                        case 'correction':
+                            if (this.callbacks.onCorrection) this.callbacks.onCorrection(msg);
+                            break;
                         case 'userDisconnected':
                             if (this.callbacks.onUserDisconnected) this.callbacks.onUserDisconnected(msg.id);
                             break;
                         case 'chat':
                             if (this.callbacks.onChat) this.callbacks.onChat(msg);
                             break;
+                        case 'chatRejected':
+                            if (this.callbacks.onChatRejected) this.callbacks.onChatRejected(msg.reason);
+                            break;
                     }
                 } catch (e) {
                     console.error('Error parsing message:', e);
                 }
             };
 
-            this.socket.onclose = () => {
+            this.socket.onclose = (event) => {
                 console.log('Disconnected from server');
+                // disconnect() clears the socket; anything else was a drop
+                if (this.socket !== socket) return;
+                // A ban, or a refused upgrade, would only be refused again
+                if (event.code === POLICY_VIOLATION || !opened) {
+                    this.resumeToken = null;
+                    return;
+                }
+                if (!this.resumeToken) return;
+
+                const delay = Math.min(
+                    RECONNECT_DELAY_MS * 2 ** this.reconnectAttempts,
+                    MAX_RECONNECT_DELAY_MS
+                );
+                this.reconnectAttempts += 1;
+                setTimeout(() => {
+                    if (this.socket === socket) this.connect();
+                }, delay);
             };
 
             this.socket.onerror = (error) => {
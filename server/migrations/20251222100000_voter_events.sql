@@ -0,0 +1,63 @@
+-- OligarchyVoter events: bribes, seizures, votes and claims
+CREATE TABLE IF NOT EXISTS oligarchy.bribe_deposits (
+    id BIGSERIAL PRIMARY KEY,
+    epoch BIGINT NOT NULL,
+    region_id BIGINT NOT NULL,
+    depositor VARCHAR(42) NOT NULL, -- tx sender (the farm exit tax is deposited on behalf of the withdrawing user)
+    amount NUMERIC(78, 0) NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bribe_deposits_epoch_region
+    ON oligarchy.bribe_deposits (epoch, region_id);
+
+CREATE TABLE IF NOT EXISTS oligarchy.bribe_seizures (
+    id BIGSERIAL PRIMARY KEY,
+    epoch BIGINT NOT NULL,
+    from_region BIGINT NOT NULL,
+    to_region BIGINT NOT NULL,
+    amount NUMERIC(78, 0) NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bribe_seizures_epoch
+    ON oligarchy.bribe_seizures (epoch);
+
+CREATE TABLE IF NOT EXISTS oligarchy.votes (
+    id BIGSERIAL PRIMARY KEY,
+    epoch BIGINT NOT NULL,
+    region_id BIGINT NOT NULL,
+    voter VARCHAR(42) NOT NULL,
+    weight NUMERIC(78, 0) NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_votes_epoch_region ON oligarchy.votes (epoch, region_id);
+CREATE INDEX IF NOT EXISTS idx_votes_voter ON oligarchy.votes (voter);
+
+CREATE TABLE IF NOT EXISTS oligarchy.bribe_claims (
+    id BIGSERIAL PRIMARY KEY,
+    epoch BIGINT, -- decoded from claimBribe calldata, NULL if unavailable
+    region_id BIGINT,
+    voter VARCHAR(42) NOT NULL,
+    amount NUMERIC(78, 0) NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bribe_claims_voter ON oligarchy.bribe_claims (voter);
@@ -0,0 +1,37 @@
+-- WarTheater: declared wars, troop enlistments and results
+CREATE TABLE IF NOT EXISTS oligarchy.wars (
+    id BIGSERIAL PRIMARY KEY,
+    epoch BIGINT NOT NULL,
+    attacker_region BIGINT NOT NULL,
+    defender_region BIGINT NOT NULL,
+    resolved BOOLEAN NOT NULL DEFAULT FALSE,
+    attacker_won BOOLEAN, -- NULL until WarResult
+    declared_block BIGINT NOT NULL,
+    declared_tx_hash VARCHAR(66) NOT NULL,
+    resolved_block BIGINT,
+    resolved_tx_hash VARCHAR(66),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (epoch, attacker_region) -- WarTheater allows one target per attacker per epoch
+);
+
+CREATE INDEX IF NOT EXISTS idx_wars_defender ON oligarchy.wars (epoch, defender_region);
+
+CREATE TABLE IF NOT EXISTS oligarchy.war_enlistments (
+    id BIGSERIAL PRIMARY KEY,
+    epoch BIGINT NOT NULL,
+    region_id BIGINT NOT NULL,
+    wallet_address VARCHAR(42) NOT NULL,
+    amount NUMERIC(78, 0) NOT NULL, -- OLIG burned as troops
+    is_attack BOOLEAN NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_war_enlistments_epoch_region
+    ON oligarchy.war_enlistments (epoch, region_id);
+CREATE INDEX IF NOT EXISTS idx_war_enlistments_wallet
+    ON oligarchy.war_enlistments (wallet_address);
@@ -0,0 +1,78 @@
+-- RegionPolitics: elections, governors and revolutions
+CREATE TABLE IF NOT EXISTS oligarchy.election_candidates (
+    region_id BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    candidate VARCHAR(42) NOT NULL,
+    guild_name TEXT NOT NULL,
+    total_votes NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (region_id, epoch, candidate)
+);
+
+CREATE TABLE IF NOT EXISTS oligarchy.election_votes (
+    id BIGSERIAL PRIMARY KEY,
+    region_id BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    voter VARCHAR(42) NOT NULL,
+    candidate VARCHAR(42) NOT NULL,
+    weight NUMERIC(78, 0) NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_election_votes_region_epoch
+    ON oligarchy.election_votes (region_id, epoch);
+
+-- Keyed by the epoch the election was held in; the governor serves in epoch + 1.
+CREATE TABLE IF NOT EXISTS oligarchy.governors (
+    region_id BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    governor VARCHAR(42) NOT NULL,
+    guild_name TEXT NOT NULL DEFAULT '',
+    votes NUMERIC(78, 0) NOT NULL,
+    ousted BOOLEAN NOT NULL DEFAULT FALSE,
+    ousted_epoch BIGINT,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (region_id, epoch)
+);
+
+CREATE TABLE IF NOT EXISTS oligarchy.revolutions (
+    region_id BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    provocateur VARCHAR(42) NOT NULL,
+    started_at BIGINT NOT NULL, -- block timestamp; support closes 3 days later
+    provocateur_power NUMERIC(78, 0) NOT NULL DEFAULT 0, -- provocateur's veOLIG at started_at, counted by the contract
+    total_support NUMERIC(78, 0) NOT NULL DEFAULT 0, -- provocateur_power plus every supporter's weight
+    executed BOOLEAN NOT NULL DEFAULT FALSE,
+    executed_block BIGINT,
+    ousted_governor VARCHAR(42),
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (region_id, epoch)
+);
+
+CREATE TABLE IF NOT EXISTS oligarchy.revolution_supporters (
+    id BIGSERIAL PRIMARY KEY,
+    region_id BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    supporter VARCHAR(42) NOT NULL,
+    weight NUMERIC(78, 0) NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_revolution_supporters_region_epoch
+    ON oligarchy.revolution_supporters (region_id, epoch);
@@ -0,0 +1,30 @@
+-- LandGenesis NFTs: tier and current owner per token
+CREATE TABLE IF NOT EXISTS oligarchy.land_tokens (
+    token_id BIGINT PRIMARY KEY,
+    tier BIGINT NOT NULL,
+    vote_multiplier BIGINT NOT NULL, -- boost percent of the tier, fixed in the constructor
+    owner VARCHAR(42) NOT NULL,
+    minted_by VARCHAR(42) NOT NULL,
+    mint_block BIGINT NOT NULL,
+    mint_tx_hash VARCHAR(66) NOT NULL,
+    last_transfer_block BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_land_tokens_owner ON oligarchy.land_tokens (owner);
+
+-- Raw transfers, so owners can be rebuilt after a reorg
+CREATE TABLE IF NOT EXISTS oligarchy.land_transfers (
+    id BIGSERIAL PRIMARY KEY,
+    token_id BIGINT NOT NULL,
+    from_address VARCHAR(42) NOT NULL,
+    to_address VARCHAR(42) NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_land_transfers_token ON oligarchy.land_transfers (token_id);
@@ -0,0 +1,28 @@
+-- VeOligarchy: current lock per wallet (createLock requires the previous one withdrawn)
+CREATE TABLE IF NOT EXISTS oligarchy.ve_locks (
+    wallet_address VARCHAR(42) PRIMARY KEY,
+    amount NUMERIC(78, 0) NOT NULL DEFAULT 0, -- 0 once withdrawn
+    unlock_time BIGINT NOT NULL DEFAULT 0, -- unix seconds, rounded down to the week
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ve_locks_unlock_time ON oligarchy.ve_locks (unlock_time);
+
+-- Raw lock deposits and withdrawals, so locks can be rebuilt after a reorg
+CREATE TABLE IF NOT EXISTS oligarchy.ve_lock_events (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address VARCHAR(42) NOT NULL,
+    is_deposit BOOLEAN NOT NULL,
+    amount NUMERIC(78, 0) NOT NULL,
+    unlock_time BIGINT NOT NULL DEFAULT 0,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ve_lock_events_wallet ON oligarchy.ve_lock_events (wallet_address);
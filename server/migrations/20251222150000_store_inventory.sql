@@ -0,0 +1,30 @@
+-- GameStore: item catalog, purchase log and per-wallet inventory
+CREATE TABLE IF NOT EXISTS oligarchy.store_items (
+    item_id BIGINT PRIMARY KEY,
+    name TEXT NOT NULL,
+    price NUMERIC(78, 0) NOT NULL, -- OLIG burned per purchase
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS oligarchy.item_purchases (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address VARCHAR(42) NOT NULL,
+    item_id BIGINT NOT NULL,
+    price NUMERIC(78, 0) NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS oligarchy.player_inventory (
+    wallet_address VARCHAR(42) NOT NULL,
+    item_id BIGINT NOT NULL,
+    quantity BIGINT NOT NULL DEFAULT 0, -- consumables can be bought repeatedly
+    total_spent NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (wallet_address, item_id)
+);
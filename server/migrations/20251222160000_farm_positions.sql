@@ -0,0 +1,32 @@
+-- RegionFarmDynamic: stake per wallet per pool (pid = region id)
+CREATE TABLE IF NOT EXISTS oligarchy.farm_positions (
+    wallet_address VARCHAR(42) NOT NULL,
+    pid BIGINT NOT NULL,
+    staked NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    total_deposited NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    total_withdrawn NUMERIC(78, 0) NOT NULL DEFAULT 0, -- gross, i.e. net received + tax
+    total_tax_paid NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (wallet_address, pid)
+);
+
+CREATE INDEX IF NOT EXISTS idx_farm_positions_pid ON oligarchy.farm_positions (pid);
+
+-- Raw deposits and withdrawals, so positions can be rebuilt after a reorg
+CREATE TABLE IF NOT EXISTS oligarchy.farm_events (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_address VARCHAR(42) NOT NULL,
+    pid BIGINT NOT NULL,
+    is_deposit BOOLEAN NOT NULL,
+    amount NUMERIC(78, 0) NOT NULL, -- net of tax for withdrawals, as emitted
+    tax NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    epoch BIGINT NOT NULL, -- game epoch the log was mined in, for per-epoch leaderboards
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_farm_events_block ON oligarchy.farm_events (block_number);
@@ -0,0 +1,7 @@
+-- Reorg safety: hashes of indexed blocks, so a fork can be detected and the
+-- indexed data above it rolled back
+CREATE TABLE IF NOT EXISTS oligarchy.indexed_blocks (
+    block_number BIGINT PRIMARY KEY,
+    block_hash VARCHAR(66) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,12 @@
+-- One row per log and contract kind the indexer has applied it for, so
+-- replaying a block range is a no-op
+CREATE TABLE IF NOT EXISTS oligarchy.applied_events (
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    contract_kind VARCHAR(32) NOT NULL,
+    block_number BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tx_hash, log_index, contract_kind)
+);
+
+CREATE INDEX IF NOT EXISTS idx_applied_events_block ON oligarchy.applied_events (block_number);
@@ -0,0 +1,14 @@
+-- One cursor per indexed contract, replacing the single-row indexer_state.
+-- Cursors are kept per contract kind rather than per address: several kinds
+-- can share an address, and a kind added later starts from its own start
+-- block and backfills while the others keep tailing the chain.
+CREATE TABLE IF NOT EXISTS oligarchy.indexer_cursors (
+    contract_address VARCHAR(42) NOT NULL,
+    contract_kind VARCHAR(32) NOT NULL,
+    start_block BIGINT NOT NULL,
+    last_processed_block BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (contract_address, contract_kind)
+);
+
+DROP TABLE IF EXISTS oligarchy.indexer_state;
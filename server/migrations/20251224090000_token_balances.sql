@@ -0,0 +1,25 @@
+-- ERC-20 transfers of OLIG and mETH, and the balances they add up to
+CREATE TABLE IF NOT EXISTS oligarchy.token_transfers (
+    id BIGSERIAL PRIMARY KEY,
+    token_address VARCHAR(42) NOT NULL,
+    from_address VARCHAR(42) NOT NULL,
+    to_address VARCHAR(42) NOT NULL,
+    amount NUMERIC(78, 0) NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_token_transfers_block ON oligarchy.token_transfers (block_number);
+
+CREATE TABLE IF NOT EXISTS oligarchy.token_balances (
+    token_address VARCHAR(42) NOT NULL,
+    wallet_address VARCHAR(42) NOT NULL,
+    balance NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (token_address, wallet_address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_token_balances_wallet ON oligarchy.token_balances (wallet_address);
@@ -0,0 +1,42 @@
+-- Scores per board, wallet and epoch, plus an all-time row with epoch -1.
+-- The indexer refreshes the view after every range that applied new logs.
+--   stakers:   net farm stake added (all-time: current stake)
+--   voters:    vote weight cast in OligarchyVoter
+--   warlords:  troops enlisted on the winning side of resolved wars
+--   governors: epochs ruled (a governor elected in epoch N rules N + 1)
+--   bribers:   mETH bribes deposited
+CREATE MATERIALIZED VIEW IF NOT EXISTS oligarchy.leaderboard_scores AS
+WITH scores AS (
+    SELECT 'stakers' AS board, epoch, wallet_address,
+           CASE WHEN is_deposit THEN amount ELSE -(amount + tax) END AS score
+    FROM oligarchy.farm_events
+    UNION ALL
+    SELECT 'voters', epoch, voter, weight
+    FROM oligarchy.votes
+    UNION ALL
+    SELECT 'warlords', e.epoch, e.wallet_address, e.amount
+    FROM oligarchy.war_enlistments e
+    JOIN oligarchy.wars w
+      ON w.epoch = e.epoch
+     AND w.resolved
+     AND ((e.is_attack AND w.attacker_region = e.region_id AND w.attacker_won)
+       OR (NOT e.is_attack AND w.defender_region = e.region_id AND NOT w.attacker_won))
+    UNION ALL
+    SELECT 'governors', epoch + 1, governor, 1
+    FROM oligarchy.governors
+    UNION ALL
+    SELECT 'bribers', epoch, depositor, amount
+    FROM oligarchy.bribe_deposits
+)
+SELECT board,
+       CASE WHEN GROUPING(epoch) = 1 THEN -1 ELSE epoch END AS epoch,
+       wallet_address,
+       SUM(score) AS score
+FROM scores
+GROUP BY GROUPING SETS ((board, epoch, wallet_address), (board, wallet_address));
+
+-- Required by REFRESH MATERIALIZED VIEW CONCURRENTLY
+CREATE UNIQUE INDEX IF NOT EXISTS idx_leaderboard_scores_key
+    ON oligarchy.leaderboard_scores (board, epoch, wallet_address);
+CREATE INDEX IF NOT EXISTS idx_leaderboard_scores_rank
+    ON oligarchy.leaderboard_scores (board, epoch, score DESC);
@@ -0,0 +1,10 @@
+-- Where each wallet's avatar was last seen, so returning players spawn there.
+-- Guests have no wallet and are not stored.
+CREATE TABLE IF NOT EXISTS oligarchy.player_positions (
+    wallet_address VARCHAR(42) PRIMARY KEY,
+    scene VARCHAR(64) NOT NULL,
+    x REAL NOT NULL,
+    y REAL NOT NULL,
+    anim VARCHAR(64) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
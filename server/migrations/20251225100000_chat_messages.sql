@@ -0,0 +1,15 @@
+-- Chat history per channel: "global", "scene:<Scene>", "region:<id>",
+-- "guild:<name>" or "whisper" (then recipient_wallet is set).
+CREATE TABLE IF NOT EXISTS oligarchy.chat_messages (
+    id BIGSERIAL PRIMARY KEY,
+    channel VARCHAR(80) NOT NULL,
+    sender_id VARCHAR(64) NOT NULL,
+    sender_wallet VARCHAR(42),
+    recipient_wallet VARCHAR(42),
+    message TEXT NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_messages_channel ON oligarchy.chat_messages (channel, sent_at DESC);
+CREATE INDEX IF NOT EXISTS idx_chat_messages_sender_wallet ON oligarchy.chat_messages (sender_wallet, sent_at DESC);
+CREATE INDEX IF NOT EXISTS idx_chat_messages_recipient_wallet ON oligarchy.chat_messages (recipient_wallet, sent_at DESC);
@@ -0,0 +1,11 @@
+-- Mutes and bans per wallet, set through the admin API. A NULL expires_at
+-- never expires.
+CREATE TABLE IF NOT EXISTS oligarchy.chat_sanctions (
+    wallet_address VARCHAR(42) NOT NULL,
+    kind VARCHAR(8) NOT NULL CHECK (kind IN ('mute', 'ban')),
+    reason TEXT,
+    issued_by VARCHAR(42) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ,
+    PRIMARY KEY (wallet_address, kind)
+);
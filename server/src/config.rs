@@ -1,6 +1,12 @@
+use alloy::primitives::Address;
+use anyhow::{Context, Result};
+use axum::http::HeaderValue;
 use dotenvy::dotenv;
+use std::collections::HashMap;
 use std::env;
+use std::str::FromStr;
 
+#[allow(non_snake_case)]
 #[derive(Clone)]
 pub struct Config {
     pub database_url: String,
@@ -11,17 +17,59 @@ pub struct Config {
     pub OLIG_VOTER_ADDRESS: String,
     pub REGION_FARM_ADDRESS: String,
     pub WAR_THEATER_ADDRESS: String,
+    /// Not part of the ignition module yet; only indexed when set.
+    pub REGION_POLITICS_ADDRESS: Option<String>,
+    /// Not part of the ignition module yet; only indexed when set.
+    pub LAND_GENESIS_ADDRESS: Option<String>,
     pub database_schema_url: String,
     pub rpc_url: String,
+    /// Blocks to stay behind the chain head so shallow reorgs never reach the index.
+    pub indexer_confirmations: u64,
+    /// Upper bound on the blocks requested per `eth_getLogs` call.
+    pub indexer_max_block_range: u64,
+    /// Deployment block per contract address, from `INDEXER_START_BLOCKS`
+    /// (`0xaddr=block,...`). Contracts not listed are indexed from genesis.
+    pub indexer_start_blocks: HashMap<String, u64>,
+    /// HMAC secret for the session tokens issued after Sign-In with Ethereum.
+    pub jwt_secret: String,
+    /// Domain that sign-in messages must name, i.e. the frontend's host.
+    pub siwe_domain: String,
+    /// Origin the frontend is served from, the only one CORS allows.
+    pub frontend_origin: HeaderValue,
+    pub chain_id: u64,
+    /// Room ticks per second; moves are batched into one snapshot per room
+    /// per tick.
+    pub tick_rate: u32,
+    /// Players per scene instance before another instance is opened.
+    pub room_capacity: usize,
+    /// Seconds between saves of the positions of players with a wallet.
+    pub position_save_secs: u64,
+    /// Seconds a dropped connection may take to resume before the player
+    /// is removed from their room.
+    pub resume_grace_secs: u64,
+    /// Directory holding the frontend's Tiled maps, used to validate moves.
+    pub maps_dir: String,
+    /// Longest chat message, in characters.
+    pub chat_max_length: usize,
+    /// Chat messages a session may send per second once its burst is spent.
+    pub chat_rate_per_sec: f64,
+    /// Chat messages a session may send at once.
+    pub chat_rate_burst: f64,
+    /// Words masked out of chat, from `CHAT_BLOCKED_WORDS` (`word,...`).
+    pub chat_blocked_words: Vec<String>,
+    /// Checksummed wallets allowed to use the admin API, from
+    /// `ADMIN_WALLETS` (`0xaddr,...`).
+    pub admin_wallets: Vec<String>,
     pub port: u16,
 }
 
 impl Config {
-    pub fn from_env() -> Self {
+    #[allow(non_snake_case)]
+    pub fn from_env() -> Result<Self> {
         dotenv().ok();
-        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let database_url = env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
         let database_schema_url =
-            env::var("DATABASE_SCHEMA_URL").expect("DATABASE_SCHEMA_URL must be set");
+            env::var("DATABASE_SCHEMA_URL").context("DATABASE_SCHEMA_URL must be set")?;
         let MOCK_MANTLE_ADDRESS = env::var("MOCK_MANTLE_ADDRESS")
             .unwrap_or_else(|_| "0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string());
         let OLIG_TOKEN_ADDRESS = env::var("OLIG_TOKEN_ADDRESS")
@@ -36,13 +84,94 @@ impl Config {
             .unwrap_or_else(|_| "0x5FC8d32690cc91D4c39d9d3abcBD16989F875707".to_string());
         let WAR_THEATER_ADDRESS = env::var("WAR_THEATER_ADDRESS")
             .unwrap_or_else(|_| "0x0165878A594ca255338adfa4d48449f69242Eb8F".to_string());
+        let REGION_POLITICS_ADDRESS = env::var("REGION_POLITICS_ADDRESS").ok();
+        let LAND_GENESIS_ADDRESS = env::var("LAND_GENESIS_ADDRESS").ok();
         let rpc_url = env::var("RPC_URL").unwrap_or_else(|_| "http://127.0.0.1:8545".to_string());
+        let indexer_confirmations = env::var("INDEXER_CONFIRMATIONS")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .context("INDEXER_CONFIRMATIONS must be a number")?;
+        let indexer_max_block_range = env::var("INDEXER_MAX_BLOCK_RANGE")
+            .unwrap_or_else(|_| "2000".to_string())
+            .parse()
+            .context("INDEXER_MAX_BLOCK_RANGE must be a number")?;
+        let indexer_start_blocks = env::var("INDEXER_START_BLOCKS")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| {
+                let (addr, block) = entry
+                    .split_once('=')
+                    .context("INDEXER_START_BLOCKS entries must be address=block")?;
+                let block = block
+                    .trim()
+                    .parse()
+                    .context("INDEXER_START_BLOCKS blocks must be numbers")?;
+                Ok((addr.trim().to_lowercase(), block))
+            })
+            .collect::<Result<_>>()?;
+        let jwt_secret = env::var("JWT_SECRET").context("JWT_SECRET must be set")?;
+        let siwe_domain = env::var("SIWE_DOMAIN").unwrap_or_else(|_| "localhost:5173".to_string());
+        let frontend_origin = env::var("FRONTEND_ORIGIN")
+            .unwrap_or_else(|_| format!("http://{}", siwe_domain))
+            .parse()
+            .context("FRONTEND_ORIGIN must be an origin")?;
+        let chain_id = env::var("CHAIN_ID")
+            .unwrap_or_else(|_| "31337".to_string())
+            .parse()
+            .context("CHAIN_ID must be a number")?;
+        let tick_rate = env::var("TICK_RATE")
+            .unwrap_or_else(|_| "20".to_string())
+            .parse()
+            .context("TICK_RATE must be a number")?;
+        let room_capacity = env::var("ROOM_CAPACITY")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .context("ROOM_CAPACITY must be a number")?;
+        let position_save_secs = env::var("POSITION_SAVE_SECS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .context("POSITION_SAVE_SECS must be a number")?;
+        let resume_grace_secs = env::var("RESUME_GRACE_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .context("RESUME_GRACE_SECS must be a number")?;
+        let maps_dir =
+            env::var("MAPS_DIR").unwrap_or_else(|_| "../front-end/src/assets/game".to_string());
+        let chat_max_length = env::var("CHAT_MAX_LENGTH")
+            .unwrap_or_else(|_| "280".to_string())
+            .parse()
+            .context("CHAT_MAX_LENGTH must be a number")?;
+        let chat_rate_per_sec = env::var("CHAT_RATE_PER_SEC")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse()
+            .context("CHAT_RATE_PER_SEC must be a number")?;
+        let chat_rate_burst = env::var("CHAT_RATE_BURST")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .context("CHAT_RATE_BURST must be a number")?;
+        let chat_blocked_words = env::var("CHAT_BLOCKED_WORDS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let admin_wallets = env::var("ADMIN_WALLETS")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| {
+                Address::from_str(entry.trim())
+                    .map(|address| address.to_string())
+                    .context("ADMIN_WALLETS entries must be addresses")
+            })
+            .collect::<Result<_>>()?;
         let port = env::var("PORT")
             .unwrap_or_else(|_| "8000".to_string())
             .parse()
-            .expect("PORT must be a number");
+            .context("PORT must be a number")?;
 
-        Self {
+        Ok(Self {
             database_url,
             database_schema_url,
             MOCK_MANTLE_ADDRESS,
@@ -52,8 +181,34 @@ impl Config {
             OLIG_VOTER_ADDRESS,
             REGION_FARM_ADDRESS,
             WAR_THEATER_ADDRESS,
+            REGION_POLITICS_ADDRESS,
+            LAND_GENESIS_ADDRESS,
             rpc_url,
+            indexer_confirmations,
+            indexer_max_block_range,
+            indexer_start_blocks,
+            jwt_secret,
+            siwe_domain,
+            frontend_origin,
+            chain_id,
+            tick_rate,
+            room_capacity,
+            position_save_secs,
+            resume_grace_secs,
+            maps_dir,
+            chat_max_length,
+            chat_rate_per_sec,
+            chat_rate_burst,
+            chat_blocked_words,
+            admin_wallets,
             port,
-        }
+        })
+    }
+
+    pub fn start_block(&self, address: &str) -> u64 {
+        self.indexer_start_blocks
+            .get(&address.to_lowercase())
+            .copied()
+            .unwrap_or(0)
     }
 }
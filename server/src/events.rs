@@ -0,0 +1,39 @@
+use tokio::sync::broadcast;
+
+/// Buffered events per subscriber before slow receivers start lagging.
+pub const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Chain events the game reacts to. The indexer publishes them only after the
+/// block range containing them is committed, and never for replayed logs.
+#[derive(Clone, Debug)]
+pub enum DomainEvent {
+    WarResult {
+        epoch: i64,
+        attacker_region: i64,
+        defender_region: i64,
+        success: bool,
+    },
+    GovernorElected {
+        region_id: i64,
+        epoch: i64,
+        governor: String,
+        votes: String,
+    },
+    BribeSeized {
+        epoch: i64,
+        from_region: i64,
+        to_region: i64,
+        amount: String,
+    },
+    LandMinted {
+        token_id: i64,
+        tier: i64,
+        owner: String,
+    },
+}
+
+pub type EventBus = broadcast::Sender<DomainEvent>;
+
+pub fn event_bus() -> EventBus {
+    broadcast::channel(EVENT_BUS_CAPACITY).0
+}
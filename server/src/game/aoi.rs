@@ -0,0 +1,131 @@
+use std::collections::{HashMap, HashSet};
+
+/// Side of a grid cell in pixels. Players see the cells around their own,
+/// roughly one zoomed-in viewport.
+pub const CELL_SIZE: f32 = 320.0;
+
+pub type Cell = (i32, i32);
+
+pub fn cell_of(x: f32, y: f32) -> Cell {
+    (
+        (x / CELL_SIZE).floor() as i32,
+        (y / CELL_SIZE).floor() as i32,
+    )
+}
+
+/// Spatial index of the players in a room. Two players are in view of each
+/// other when their cells are at most one apart, so interest is symmetric.
+#[derive(Debug, Default)]
+pub struct Grid {
+    cells: HashMap<Cell, HashSet<String>>,
+}
+
+impl Grid {
+    pub fn insert(&mut self, id: &str, cell: Cell) {
+        self.cells.entry(cell).or_default().insert(id.to_string());
+    }
+
+    pub fn remove(&mut self, id: &str, cell: Cell) {
+        if let Some(ids) = self.cells.get_mut(&cell) {
+            ids.remove(id);
+            if ids.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    pub fn relocate(&mut self, id: &str, from: Cell, to: Cell) {
+        self.remove(id, from);
+        self.insert(id, to);
+    }
+
+    /// Players within the interest radius of `cell`, other than `id`.
+    pub fn nearby(&self, id: &str, cell: Cell) -> HashSet<String> {
+        let (cx, cy) = cell;
+        let mut ids = HashSet::new();
+        for x in cx - 1..=cx + 1 {
+            for y in cy - 1..=cy + 1 {
+                if let Some(cell) = self.cells.get(&(x, y)) {
+                    ids.extend(cell.iter().filter(|other| *other != id).cloned());
+                }
+            }
+        }
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_of(players: &[(&str, f32, f32)]) -> Grid {
+        let mut grid = Grid::default();
+        for (id, x, y) in players {
+            grid.insert(id, cell_of(*x, *y));
+        }
+        grid
+    }
+
+    #[test]
+    fn floors_negative_positions() {
+        assert_eq!(cell_of(0.0, 0.0), (0, 0));
+        assert_eq!(cell_of(319.9, 320.0), (0, 1));
+        assert_eq!(cell_of(-0.1, -320.1), (-1, -2));
+    }
+
+    #[test]
+    fn sees_the_surrounding_cells_only() {
+        let grid = grid_of(&[
+            ("me", 500.0, 500.0),
+            ("same", 600.0, 600.0),
+            ("diagonal", 100.0, 900.0),
+            ("far", 1300.0, 500.0),
+        ]);
+
+        let nearby = grid.nearby("me", cell_of(500.0, 500.0));
+
+        assert!(nearby.contains("same"));
+        assert!(nearby.contains("diagonal"));
+        assert!(!nearby.contains("far"));
+        assert!(!nearby.contains("me"));
+    }
+
+    #[test]
+    fn interest_is_symmetric() {
+        let players: Vec<(String, f32, f32)> = (0..64)
+            .map(|i| {
+                let (x, y) = ((i * 137 % 1600) as f32, (i * 211 % 1200) as f32 - 300.0);
+                (format!("p{}", i), x, y)
+            })
+            .collect();
+        let refs: Vec<(&str, f32, f32)> = players
+            .iter()
+            .map(|(id, x, y)| (id.as_str(), *x, *y))
+            .collect();
+        let grid = grid_of(&refs);
+
+        for (a, ax, ay) in &refs {
+            let seen_by_a = grid.nearby(a, cell_of(*ax, *ay));
+            for (b, bx, by) in &refs {
+                let seen_by_b = grid.nearby(b, cell_of(*bx, *by));
+                assert_eq!(
+                    seen_by_a.contains(*b),
+                    seen_by_b.contains(*a),
+                    "{} and {} disagree",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn relocate_moves_a_player_between_cells() {
+        let mut grid = grid_of(&[("me", 0.0, 0.0), ("mover", 100.0, 100.0)]);
+
+        grid.relocate("mover", cell_of(100.0, 100.0), cell_of(2000.0, 100.0));
+
+        assert!(grid.nearby("me", cell_of(0.0, 0.0)).is_empty());
+        assert!(grid.nearby("me", cell_of(2000.0, 100.0)).contains("mover"));
+    }
+}
@@ -0,0 +1,395 @@
+use crate::game::moderation::{ChatFilter, TokenBucket};
+use crate::game::room;
+use crate::models::chat::{ChatEntry, GLOBAL_CHANNEL, WHISPER_CHANNEL};
+use crate::models::game::GameMessage;
+use crate::models::moderation::Sanction;
+use crate::repositories::chat_repo::ChatRepository;
+use crate::state::ClientSender;
+use anyhow::{Result, bail};
+use axum::extract::ws::{CloseFrame, Message, close_code};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+/// Messages sent along when a client joins a channel.
+const HISTORY_LIMIT: i64 = 50;
+
+/// Protocol of clients that only know the scene-wide `chat` message.
+pub const LEGACY_PROTOCOL: u8 = 1;
+/// Current protocol: chat channels, history and whispers.
+pub const PROTOCOL_VERSION: u8 = 2;
+
+struct Subscriber {
+    sender: ClientSender,
+    wallet: Option<String>,
+    /// Address the client connected from.
+    address: IpAddr,
+    protocol: u8,
+    channels: HashSet<String>,
+    mute: Option<Sanction>,
+}
+
+impl Subscriber {
+    /// Whose allowance the session's messages use up: the wallet's, or for
+    /// guests that of every guest at the same address, so reconnecting
+    /// does not refill it.
+    fn rate_key(&self) -> String {
+        match &self.wallet {
+            Some(wallet) => wallet.clone(),
+            None => self.address.to_string(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Subscriptions {
+    subscribers: HashMap<String, Subscriber>,
+    channels: HashMap<String, HashSet<String>>,
+    buckets: HashMap<String, TokenBucket>,
+    /// Mutes and bans of wallets by the addresses they connected from.
+    /// Guests at those addresses get the same, so signing out sheds
+    /// neither.
+    address_mutes: HashMap<IpAddr, Sanction>,
+    address_bans: HashMap<IpAddr, Sanction>,
+}
+
+impl Subscriptions {
+    /// Runs a message through mutes and the filter.
+    fn moderate(&mut self, filter: &ChatFilter, id: &str, message: &str) -> Result<String> {
+        let Some(subscriber) = self.subscribers.get(id) else {
+            bail!("not connected to chat");
+        };
+        let mute = match &subscriber.wallet {
+            Some(_) => subscriber.mute.as_ref(),
+            None => self.address_mutes.get(&subscriber.address),
+        };
+        if let Some(mute) = mute
+            && mute.is_active()
+        {
+            bail!("{}", mute.describe());
+        }
+        let bucket = self
+            .buckets
+            .entry(subscriber.rate_key())
+            .or_insert_with(|| filter.bucket());
+        filter.check(bucket, message)
+    }
+
+    /// Addresses the sessions of a wallet connected from.
+    fn addresses_of(&self, wallet: &str) -> Vec<IpAddr> {
+        self.subscribers
+            .values()
+            .filter(|subscriber| subscriber.wallet.as_deref() == Some(wallet))
+            .map(|subscriber| subscriber.address)
+            .collect()
+    }
+
+    fn join(&mut self, id: &str, channel: &str) -> bool {
+        let Some(subscriber) = self.subscribers.get_mut(id) else {
+            return false;
+        };
+        if !subscriber.channels.insert(channel.to_string()) {
+            return false;
+        }
+        self.channels
+            .entry(channel.to_string())
+            .or_default()
+            .insert(id.to_string());
+        true
+    }
+
+    fn leave(&mut self, id: &str, channel: &str) {
+        if let Some(subscriber) = self.subscribers.get_mut(id) {
+            subscriber.channels.remove(channel);
+        }
+        if let Some(ids) = self.channels.get_mut(channel) {
+            ids.remove(id);
+            if ids.is_empty() {
+                self.channels.remove(channel);
+            }
+        }
+    }
+}
+
+/// Chat channels of every connected session, independent of rooms so that
+/// global, region and guild channels span scenes and instances.
+#[derive(Clone)]
+pub struct ChatHub {
+    db: PgPool,
+    filter: ChatFilter,
+    subscriptions: Arc<Mutex<Subscriptions>>,
+}
+
+impl ChatHub {
+    pub fn new(db: PgPool, filter: ChatFilter) -> Self {
+        Self {
+            db,
+            filter,
+            subscriptions: Arc::new(Mutex::new(Subscriptions::default())),
+        }
+    }
+
+    /// Registers a session and joins it to the global channel. Wallet
+    /// sessions also get their recent whispers. `mute` is the wallet's
+    /// mute, if it has one.
+    pub async fn connect(
+        &self,
+        id: &str,
+        wallet: Option<String>,
+        address: IpAddr,
+        protocol: u8,
+        mute: Option<Sanction>,
+        sender: ClientSender,
+    ) {
+        {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            subscriptions
+                .buckets
+                .retain(|_, bucket| !self.filter.is_full(bucket));
+            if wallet.is_some()
+                && let Some(mute) = &mute
+            {
+                subscriptions.address_mutes.insert(address, mute.clone());
+            }
+            subscriptions.subscribers.insert(
+                id.to_string(),
+                Subscriber {
+                    sender: sender.clone(),
+                    wallet: wallet.clone(),
+                    address,
+                    protocol,
+                    channels: HashSet::new(),
+                    mute,
+                },
+            );
+        }
+        self.join(id, GLOBAL_CHANNEL).await;
+
+        if protocol >= PROTOCOL_VERSION
+            && let Some(wallet) = wallet
+        {
+            match ChatRepository::new(self.db.clone())
+                .find_whispers(&wallet, HISTORY_LIMIT)
+                .await
+            {
+                Ok(messages) => room::send(
+                    &sender,
+                    &GameMessage::ChatHistory {
+                        channel: WHISPER_CHANNEL.to_string(),
+                        messages,
+                    },
+                ),
+                Err(e) => eprintln!("Failed to load whispers for {}: {:?}", wallet, e),
+            }
+        }
+    }
+
+    pub fn disconnect(&self, id: &str) {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let Some(subscriber) = subscriptions.subscribers.get(id) else {
+            return;
+        };
+        for channel in subscriber.channels.clone() {
+            subscriptions.leave(id, &channel);
+        }
+        subscriptions.subscribers.remove(id);
+    }
+
+    /// Joins a channel and sends its recent history to protocol 2 clients.
+    pub async fn join(&self, id: &str, channel: &str) {
+        let sender = {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            if !subscriptions.join(id, channel) {
+                return;
+            }
+            let subscriber = &subscriptions.subscribers[id];
+            if subscriber.protocol < PROTOCOL_VERSION {
+                return;
+            }
+            subscriber.sender.clone()
+        };
+
+        match ChatRepository::new(self.db.clone())
+            .find_history(channel, HISTORY_LIMIT)
+            .await
+        {
+            Ok(messages) => room::send(
+                &sender,
+                &GameMessage::ChatHistory {
+                    channel: channel.to_string(),
+                    messages,
+                },
+            ),
+            Err(e) => eprintln!("Failed to load chat history of {}: {:?}", channel, e),
+        }
+    }
+
+    pub fn leave(&self, id: &str, channel: &str) {
+        self.subscriptions.lock().unwrap().leave(id, channel);
+    }
+
+    /// Sends a message to a channel the session has joined, once it passes
+    /// moderation.
+    pub fn publish(&self, id: &str, channel: &str, message: &str) -> Result<()> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let Some(subscriber) = subscriptions.subscribers.get_mut(id) else {
+            bail!("not connected to chat");
+        };
+        if !subscriber.channels.contains(channel) {
+            bail!("not in channel {}", channel);
+        }
+        let wallet = subscriber.wallet.clone();
+        let message = subscriptions.moderate(&self.filter, id, message)?;
+
+        let entry = ChatEntry {
+            channel: channel.to_string(),
+            from: id.to_string(),
+            wallet,
+            to: None,
+            message,
+            sent_at: chrono::Utc::now(),
+        };
+        let current = GameMessage::ChatMessage(entry.clone());
+        // Protocol 1 clients only ever had scene chat
+        let legacy = channel.starts_with("scene:").then(|| GameMessage::Chat {
+            id: id.to_string(),
+            message: entry.message.clone(),
+        });
+
+        let recipients = subscriptions.channels.get(channel).into_iter().flatten();
+        for recipient in recipients.filter(|recipient| *recipient != id) {
+            let Some(recipient) = subscriptions.subscribers.get(recipient) else {
+                continue;
+            };
+            if recipient.protocol >= PROTOCOL_VERSION {
+                room::send(&recipient.sender, &current);
+            } else if let Some(legacy) = &legacy {
+                room::send(&recipient.sender, legacy);
+            }
+        }
+        drop(subscriptions);
+
+        self.record(entry);
+        Ok(())
+    }
+
+    /// Sends a message to every protocol 2 session of a wallet. Only signed
+    /// in sessions may whisper, so replies have somewhere to go.
+    pub fn whisper(&self, id: &str, to: String, message: &str) -> Result<()> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let Some(subscriber) = subscriptions.subscribers.get_mut(id) else {
+            bail!("not connected to chat");
+        };
+        let Some(wallet) = subscriber.wallet.clone() else {
+            bail!("sign in to whisper");
+        };
+        let message = subscriptions.moderate(&self.filter, id, message)?;
+
+        let entry = ChatEntry {
+            channel: WHISPER_CHANNEL.to_string(),
+            from: id.to_string(),
+            wallet: Some(wallet),
+            to: Some(to),
+            message,
+            sent_at: chrono::Utc::now(),
+        };
+        let whisper_msg = GameMessage::ChatMessage(entry.clone());
+        for subscriber in subscriptions.subscribers.values() {
+            if subscriber.wallet == entry.to && subscriber.protocol >= PROTOCOL_VERSION {
+                room::send(&subscriber.sender, &whisper_msg);
+            }
+        }
+        drop(subscriptions);
+
+        self.record(entry);
+        Ok(())
+    }
+
+    /// Applies a new mute, or lifts it with `None`, for every session of
+    /// the wallet.
+    pub fn set_mute(&self, wallet: &str, mute: Option<Sanction>) {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        match &mute {
+            Some(mute) => {
+                for address in subscriptions.addresses_of(wallet) {
+                    subscriptions.address_mutes.insert(address, mute.clone());
+                }
+            }
+            None => subscriptions
+                .address_mutes
+                .retain(|_, mute| mute.wallet_address != wallet),
+        }
+        for subscriber in subscriptions.subscribers.values_mut() {
+            if subscriber.wallet.as_deref() == Some(wallet) {
+                subscriber.mute = mute.clone();
+            }
+        }
+    }
+
+    /// Closes the connections of a wallet that was just banned, and those
+    /// of guests at the addresses it connected from. The socket handlers
+    /// see the client's close and end the sessions.
+    pub fn close_wallet(&self, wallet: &str, ban: &Sanction) {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        for address in subscriptions.addresses_of(wallet) {
+            subscriptions.address_bans.insert(address, ban.clone());
+        }
+        let reason = ban.describe();
+        for subscriber in subscriptions.subscribers.values() {
+            let banned = match &subscriber.wallet {
+                Some(subscriber_wallet) => subscriber_wallet == wallet,
+                None => subscriptions
+                    .address_bans
+                    .get(&subscriber.address)
+                    .is_some_and(|ban| ban.wallet_address == wallet),
+            };
+            if banned {
+                let _ = subscriber.sender.send(Ok(Message::Close(Some(CloseFrame {
+                    code: close_code::POLICY,
+                    reason: reason.clone().into(),
+                }))));
+            }
+        }
+    }
+
+    /// Remembers the ban of a wallet turned away at `address`, so guests
+    /// connecting from there are turned away too.
+    pub fn ban_address(&self, address: IpAddr, ban: &Sanction) {
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .address_bans
+            .insert(address, ban.clone());
+    }
+
+    /// Lets guests connect again from the addresses of a wallet that was
+    /// unbanned.
+    pub fn lift_ban(&self, wallet: &str) {
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .address_bans
+            .retain(|_, ban| ban.wallet_address != wallet);
+    }
+
+    /// The active ban of a wallet that connected from `address`, if any.
+    pub fn address_ban(&self, address: IpAddr) -> Option<Sanction> {
+        let subscriptions = self.subscriptions.lock().unwrap();
+        subscriptions
+            .address_bans
+            .get(&address)
+            .filter(|ban| ban.is_active())
+            .cloned()
+    }
+
+    /// Stores a message in the background; delivery does not wait for it.
+    fn record(&self, entry: ChatEntry) {
+        let chat_repo = ChatRepository::new(self.db.clone());
+        tokio::spawn(async move {
+            if let Err(e) = chat_repo.record_message(&entry).await {
+                eprintln!("Failed to record chat message: {:?}", e);
+            }
+        });
+    }
+}
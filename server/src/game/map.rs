@@ -0,0 +1,227 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Size of the Phaser canvas, which bounds scenes that have no tilemap.
+const VIEWPORT_WIDTH: f32 = 1280.0;
+const VIEWPORT_HEIGHT: f32 = 720.0;
+
+struct SceneSpec {
+    scene: &'static str,
+    /// Tiled map under the maps directory, as loaded by the frontend scene.
+    map: Option<&'static str>,
+    /// Tile layers the frontend collides with; group layers are `Group/Layer`.
+    solid_layers: &'static [&'static str],
+    /// Where the frontend scene creates its player, from the map's centre.
+    spawn: (f32, f32),
+    /// Scenes the frontend can switch to from this one, counting those it
+    /// passes through without players, like the region grid.
+    exits: &'static [&'static str],
+}
+
+/// Scenes that players may be in. Moves into any other scene are rejected.
+const SCENES: &[SceneSpec] = &[
+    SceneSpec {
+        scene: "CapitalScene",
+        map: Some("maps/capital.tmj"),
+        solid_layers: &["Walls", "Props/Design"],
+        spawn: (-15.0, -350.0),
+        exits: &["WorldMapScene", "EstateScene"],
+    },
+    SceneSpec {
+        scene: "WorldMapScene",
+        map: Some("worldmaps/worldMap.tmj"),
+        solid_layers: &[],
+        spawn: (0.0, 0.0),
+        exits: &["CapitalScene", "EstateScene"],
+    },
+    SceneSpec {
+        scene: "EstateScene",
+        map: None,
+        solid_layers: &[],
+        spawn: (0.0, 0.0),
+        exits: &["CapitalScene", "WorldMapScene"],
+    },
+];
+
+#[derive(Deserialize)]
+struct TiledMap {
+    width: usize,
+    height: usize,
+    tilewidth: f32,
+    tileheight: f32,
+    layers: Vec<TiledLayer>,
+}
+
+#[derive(Deserialize)]
+struct TiledLayer {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    /// Global tile ids, row by row; 0 is an empty cell.
+    #[serde(default)]
+    data: Vec<u32>,
+    #[serde(default)]
+    layers: Vec<TiledLayer>,
+}
+
+/// Walkable area of one scene: its pixel bounds and the tiles players
+/// cannot stand on.
+#[derive(Debug, Clone)]
+pub struct SceneMap {
+    pub width: f32,
+    pub height: f32,
+    /// Where players entering the scene are put.
+    pub spawn: (f32, f32),
+    tile_width: f32,
+    tile_height: f32,
+    columns: usize,
+    solid: Vec<bool>,
+}
+
+impl SceneMap {
+    /// A scene without tiles, bounded by the canvas.
+    fn open(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            spawn: (width / 2.0, height / 2.0),
+            tile_width: width,
+            tile_height: height,
+            columns: 1,
+            solid: vec![false],
+        }
+    }
+
+    fn from_tiled(map: TiledMap, solid_layers: &[&str]) -> Result<Self> {
+        let mut solid = vec![false; map.width * map.height];
+        for name in solid_layers {
+            let layer = find_layer(&map.layers, name)
+                .with_context(|| format!("tile layer {} not found", name))?;
+            if layer.data.len() != solid.len() {
+                bail!("tile layer {} does not cover the map", name);
+            }
+            for (cell, gid) in solid.iter_mut().zip(&layer.data) {
+                *cell |= *gid != 0;
+            }
+        }
+
+        let width = map.width as f32 * map.tilewidth;
+        let height = map.height as f32 * map.tileheight;
+        Ok(Self {
+            width,
+            height,
+            spawn: (width / 2.0, height / 2.0),
+            tile_width: map.tilewidth,
+            tile_height: map.tileheight,
+            columns: map.width,
+            solid,
+        })
+    }
+
+    pub fn in_bounds(&self, x: f32, y: f32) -> bool {
+        (0.0..=self.width).contains(&x) && (0.0..=self.height).contains(&y)
+    }
+
+    pub fn clamp(&self, x: f32, y: f32) -> (f32, f32) {
+        (x.clamp(0.0, self.width), y.clamp(0.0, self.height))
+    }
+
+    /// Whether the tile under a point is solid. Points outside the map are.
+    pub fn is_solid(&self, x: f32, y: f32) -> bool {
+        if !self.in_bounds(x, y) {
+            return true;
+        }
+        // The far edges belong to the last row and column
+        let column = ((x / self.tile_width) as usize).min(self.columns - 1);
+        let rows = self.solid.len() / self.columns;
+        let row = ((y / self.tile_height) as usize).min(rows - 1);
+        self.solid[row * self.columns + column]
+    }
+
+    /// Smallest tile side, used to step along a path without skipping tiles.
+    pub fn tile_size(&self) -> f32 {
+        self.tile_width.min(self.tile_height)
+    }
+}
+
+fn find_layer<'a>(layers: &'a [TiledLayer], path: &str) -> Option<&'a TiledLayer> {
+    let (name, rest) = match path.split_once('/') {
+        Some((group, rest)) => (group, Some(rest)),
+        None => (path, None),
+    };
+    let layer = layers.iter().find(|layer| layer.name == name)?;
+    match rest {
+        Some(rest) => find_layer(&layer.layers, rest),
+        None if layer.kind == "tilelayer" => Some(layer),
+        None => None,
+    }
+}
+
+/// Collision maps of every scene, keyed by Phaser scene key.
+#[derive(Debug, Clone, Default)]
+pub struct Maps {
+    scenes: HashMap<String, SceneMap>,
+}
+
+impl Maps {
+    /// Loads the frontend's `.tmj` files from `dir`, normally
+    /// `front-end/src/assets/game`.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut scenes = HashMap::new();
+        for spec in SCENES {
+            let map = match spec.map {
+                Some(file) => {
+                    let path = dir.join(file);
+                    let json = std::fs::read_to_string(&path)
+                        .with_context(|| format!("failed to read {}", path.display()))?;
+                    let tiled: TiledMap = serde_json::from_str(&json)
+                        .with_context(|| format!("failed to parse {}", path.display()))?;
+                    SceneMap::from_tiled(tiled, spec.solid_layers)
+                        .with_context(|| format!("invalid map {}", path.display()))?
+                }
+                None => SceneMap::open(VIEWPORT_WIDTH, VIEWPORT_HEIGHT),
+            };
+            let (dx, dy) = spec.spawn;
+            let map = SceneMap {
+                spawn: map.clamp(map.spawn.0 + dx, map.spawn.1 + dy),
+                ..map
+            };
+            scenes.insert(spec.scene.to_string(), map);
+        }
+
+        Ok(Self { scenes })
+    }
+
+    pub fn get(&self, scene: &str) -> Option<&SceneMap> {
+        self.scenes.get(scene)
+    }
+
+    /// Whether players may switch from one scene to the other.
+    pub fn is_exit(&self, from: &str, to: &str) -> bool {
+        SCENES
+            .iter()
+            .any(|spec| spec.scene == from && spec.exits.contains(&to))
+    }
+}
+
+#[cfg(test)]
+impl SceneMap {
+    /// A map of square tiles drawn as rows of `.` (free) and `#` (solid).
+    pub(crate) fn from_rows(tile_size: f32, rows: &[&str]) -> Self {
+        let columns = rows[0].len();
+        Self {
+            width: columns as f32 * tile_size,
+            height: rows.len() as f32 * tile_size,
+            spawn: (0.0, 0.0),
+            tile_width: tile_size,
+            tile_height: tile_size,
+            columns,
+            solid: rows
+                .iter()
+                .flat_map(|row| row.chars().map(|tile| tile == '#'))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod aoi;
+pub mod chat;
+pub mod map;
+pub mod moderation;
+pub mod movement;
+pub mod room;
+pub mod rooms;
+pub mod sessions;
@@ -0,0 +1,195 @@
+use crate::config::Config;
+use anyhow::{Result, bail};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Allowance of chat messages of one sender, refilled at a steady rate up
+/// to a burst.
+pub struct TokenBucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn take(&mut self, rate_per_sec: f64, burst: f64) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate_per_sec).min(burst);
+        self.refilled = now;
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+/// Checks every chat message before it is delivered: rate, length and
+/// blocked words.
+#[derive(Clone)]
+pub struct ChatFilter {
+    max_length: usize,
+    rate_per_sec: f64,
+    burst: f64,
+    blocked_words: Arc<HashSet<String>>,
+}
+
+impl ChatFilter {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            max_length: config.chat_max_length,
+            rate_per_sec: config.chat_rate_per_sec,
+            burst: config.chat_rate_burst,
+            blocked_words: Arc::new(
+                config
+                    .chat_blocked_words
+                    .iter()
+                    .map(|word| word.to_lowercase())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// A full bucket for a new sender.
+    pub fn bucket(&self) -> TokenBucket {
+        TokenBucket {
+            tokens: self.burst,
+            refilled: Instant::now(),
+        }
+    }
+
+    /// Whether the bucket has refilled to the burst, so dropping it and
+    /// handing out a new one later changes nothing.
+    pub fn is_full(&self, bucket: &TokenBucket) -> bool {
+        bucket.tokens + bucket.refilled.elapsed().as_secs_f64() * self.rate_per_sec >= self.burst
+    }
+
+    /// Returns the message as it may be delivered, with blocked words
+    /// masked. Rejected messages still use up the sender's allowance.
+    pub fn check(&self, bucket: &mut TokenBucket, message: &str) -> Result<String> {
+        if !bucket.take(self.rate_per_sec, self.burst) {
+            bail!("sending messages too fast");
+        }
+        let message = message.trim();
+        if message.is_empty() {
+            bail!("empty message");
+        }
+        if message.chars().count() > self.max_length {
+            bail!("message longer than {} characters", self.max_length);
+        }
+        Ok(self.mask(message))
+    }
+
+    /// Replaces each blocked word, matched whole and ignoring case, with
+    /// asterisks.
+    fn mask(&self, message: &str) -> String {
+        if self.blocked_words.is_empty() {
+            return message.to_string();
+        }
+        let mut masked = String::with_capacity(message.len());
+        let mut word = String::new();
+        for c in message.chars().chain(std::iter::once(' ')) {
+            if c.is_alphanumeric() {
+                word.push(c);
+                continue;
+            }
+            if self.blocked_words.contains(&word.to_lowercase()) {
+                masked.extend(word.chars().map(|_| '*'));
+            } else {
+                masked.push_str(&word);
+            }
+            word.clear();
+            masked.push(c);
+        }
+        // Drops the space chained on to flush the last word
+        masked.pop();
+        masked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn filter(words: &[&str]) -> ChatFilter {
+        ChatFilter {
+            max_length: 10,
+            rate_per_sec: 1.0,
+            burst: 3.0,
+            blocked_words: Arc::new(words.iter().map(|word| word.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn masks_whole_words_ignoring_case() {
+        let filter = filter(&["darn"]);
+
+        assert_eq!(filter.mask("Darn it, DARN!"), "**** it, ****!");
+        assert_eq!(filter.mask("darned darnit"), "darned darnit");
+        assert_eq!(filter.mask("darn"), "****");
+    }
+
+    #[test]
+    fn leaves_messages_alone_without_blocked_words() {
+        assert_eq!(filter(&[]).mask("anything goes "), "anything goes ");
+    }
+
+    #[test]
+    fn rejects_empty_and_overlong_messages() {
+        let filter = filter(&[]);
+        let mut bucket = filter.bucket();
+
+        assert!(filter.check(&mut bucket, "   ").is_err());
+        assert!(filter.check(&mut bucket, "eleven char").is_err());
+        // Length is counted in characters, not bytes
+        let mut bucket = filter.bucket();
+        assert_eq!(
+            filter.check(&mut bucket, " äöüäöüäöüä ").unwrap(),
+            "äöüäöüäöüä"
+        );
+    }
+
+    #[test]
+    fn limits_a_burst() {
+        let filter = filter(&[]);
+        let mut bucket = filter.bucket();
+
+        for _ in 0..3 {
+            assert!(filter.check(&mut bucket, "hi").is_ok());
+        }
+        assert!(filter.check(&mut bucket, "hi").is_err());
+    }
+
+    #[test]
+    fn reports_a_bucket_full_once_refilled() {
+        let filter = filter(&[]);
+        let mut bucket = filter.bucket();
+        assert!(filter.is_full(&bucket));
+
+        filter.check(&mut bucket, "hi").unwrap();
+        assert!(!filter.is_full(&bucket));
+
+        bucket.refilled = Instant::now() - Duration::from_secs(1);
+        assert!(filter.is_full(&bucket));
+    }
+
+    #[test]
+    fn refills_over_time_up_to_the_burst() {
+        let mut bucket = TokenBucket {
+            tokens: 0.0,
+            refilled: Instant::now() - Duration::from_secs(2),
+        };
+
+        assert!(bucket.take(1.0, 3.0));
+        assert!(bucket.take(1.0, 3.0));
+        assert!(!bucket.take(1.0, 3.0));
+
+        bucket.refilled = Instant::now() - Duration::from_secs(60);
+        for _ in 0..3 {
+            assert!(bucket.take(1.0, 3.0));
+        }
+        assert!(!bucket.take(1.0, 3.0));
+    }
+}
@@ -0,0 +1,230 @@
+use crate::game::map::SceneMap;
+use crate::models::game::Player;
+use std::time::Instant;
+
+/// Running speed of the frontend `Player` (0.4 px/ms).
+const MAX_SPEED: f32 = 400.0;
+/// Headroom for frame timing and diagonal rounding on the client.
+const SPEED_TOLERANCE: f32 = 1.25;
+/// Seconds of movement that may arrive at once, e.g. after network jitter.
+const MAX_BURST_SECS: f32 = 0.5;
+/// The physics body sits at the sprite's feet, below its origin.
+const FEET_OFFSET: f32 = 16.0;
+
+/// Distance a player may still cover. Refills at the maximum speed, so
+/// moves arriving in bursts are accepted while teleports are not.
+#[derive(Debug)]
+pub struct MoveBudget {
+    distance: f32,
+    refilled_at: Instant,
+}
+
+impl MoveBudget {
+    /// A full budget for a player just put into a scene.
+    pub fn full() -> Self {
+        Self {
+            distance: max_distance(),
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) -> f32 {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f32();
+        self.refilled_at = now;
+        self.distance = (self.distance + elapsed * MAX_SPEED * SPEED_TOLERANCE).min(max_distance());
+        self.distance
+    }
+}
+
+fn max_distance() -> f32 {
+    MAX_SPEED * SPEED_TOLERANCE * MAX_BURST_SECS
+}
+
+/// Where a player saved at (x, y) is put back: clamped to the map, or
+/// `None` if that spot is solid.
+pub fn entry_point(map: &SceneMap, x: f32, y: f32) -> Option<(f32, f32)> {
+    if !x.is_finite() || !y.is_finite() {
+        return None;
+    }
+    let (x, y) = map.clamp(x, y);
+    (!is_blocked(map, x, y)).then_some((x, y))
+}
+
+/// Checks a move within the player's scene against the map and their speed
+/// budget, and applies the part of it that is legal. Returns the position
+/// the player actually ended up at when it differs from the request.
+pub fn apply_move(
+    map: &SceneMap,
+    player: &mut Player,
+    budget: &mut MoveBudget,
+    x: f32,
+    y: f32,
+    anim: String,
+) -> Option<(f32, f32)> {
+    let resolved = resolve(map, player, budget, x, y);
+
+    player.x = resolved.0;
+    player.y = resolved.1;
+    player.anim = anim;
+
+    (resolved != (x, y)).then_some(resolved)
+}
+
+fn resolve(map: &SceneMap, player: &Player, budget: &mut MoveBudget, x: f32, y: f32) -> (f32, f32) {
+    let current = (player.x, player.y);
+    if !x.is_finite() || !y.is_finite() {
+        return current;
+    }
+    let (x, y) = map.clamp(x, y);
+
+    // Cap the step at the distance the player could have covered
+    let (dx, dy) = (x - current.0, y - current.1);
+    let distance = (dx * dx + dy * dy).sqrt();
+    let allowed = budget.refill();
+    let scale = if distance > allowed {
+        allowed / distance
+    } else {
+        1.0
+    };
+    let (x, y) = (current.0 + dx * scale, current.1 + dy * scale);
+
+    // Walk the path so a step cannot hop over a wall
+    let (x, y) = last_free_point(map, current.0, current.1, x, y);
+    let (dx, dy) = (x - current.0, y - current.1);
+    budget.distance -= (dx * dx + dy * dy).sqrt();
+
+    (x, y)
+}
+
+fn is_blocked(map: &SceneMap, x: f32, y: f32) -> bool {
+    map.is_solid(x, (y + FEET_OFFSET).min(map.height))
+}
+
+/// Furthest point on the segment from (x0, y0) to (x1, y1) reachable without
+/// touching a solid tile. A player already stuck in a wall may move freely
+/// so they can get out.
+fn last_free_point(map: &SceneMap, x0: f32, y0: f32, x1: f32, y1: f32) -> (f32, f32) {
+    if is_blocked(map, x0, y0) {
+        return (x1, y1);
+    }
+
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let distance = (dx * dx + dy * dy).sqrt();
+    let steps = (distance / (map.tile_size() / 4.0)).ceil().max(1.0) as usize;
+    let mut free = (x0, y0);
+    for step in 1..=steps {
+        let t = step as f32 / steps as f32;
+        let (x, y) = (x0 + dx * t, y0 + dy * t);
+        if is_blocked(map, x, y) {
+            break;
+        }
+        free = (x, y);
+    }
+    free
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPEN: [&str; 4] = [
+        "................................................................",
+        "................................................................",
+        "................................................................",
+        "................................................................",
+    ];
+    /// Solid column at tiles 5 and 6, i.e. x in 160..224.
+    const WALLED: [&str; 4] = [
+        ".....##.........",
+        ".....##.........",
+        ".....##.........",
+        ".....##.........",
+    ];
+
+    fn player_at(x: f32, y: f32) -> Player {
+        Player {
+            id: "player".to_string(),
+            x,
+            y,
+            anim: "idle-down".to_string(),
+            scene: "TestScene".to_string(),
+            wallet: None,
+            cosmetics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn accepts_a_move_within_the_budget() {
+        let map = SceneMap::from_rows(32.0, &OPEN);
+        let mut player = player_at(100.0, 40.0);
+        let mut budget = MoveBudget::full();
+
+        let correction = apply_move(&map, &mut player, &mut budget, 200.0, 40.0, "run".into());
+
+        assert_eq!(correction, None);
+        assert_eq!((player.x, player.y), (200.0, 40.0));
+        assert_eq!(player.anim, "run");
+    }
+
+    #[test]
+    fn caps_a_teleport_at_the_budget() {
+        let map = SceneMap::from_rows(32.0, &OPEN);
+        let mut player = player_at(100.0, 40.0);
+        let mut budget = MoveBudget::full();
+
+        let correction = apply_move(&map, &mut player, &mut budget, 1900.0, 40.0, "run".into());
+
+        let (x, y) = correction.expect("a teleport is corrected");
+        assert!((x - (100.0 + max_distance())).abs() < 0.5, "moved to {}", x);
+        assert_eq!(y, 40.0);
+
+        // The budget is spent, so an immediate second step barely moves
+        apply_move(&map, &mut player, &mut budget, 1900.0, 40.0, "run".into());
+        assert!(player.x - x < 1.0, "moved on to {}", player.x);
+    }
+
+    #[test]
+    fn stops_in_front_of_a_wall() {
+        let map = SceneMap::from_rows(32.0, &WALLED);
+        let mut player = player_at(100.0, 40.0);
+        let mut budget = MoveBudget::full();
+
+        apply_move(&map, &mut player, &mut budget, 300.0, 40.0, "run".into());
+
+        assert!(player.x < 160.0, "walked into the wall at {}", player.x);
+        assert!(player.x > 150.0, "stopped early at {}", player.x);
+        assert_eq!(player.y, 40.0);
+    }
+
+    #[test]
+    fn lets_a_player_stuck_in_a_wall_out() {
+        let map = SceneMap::from_rows(32.0, &WALLED);
+        let player = player_at(180.0, 40.0);
+
+        assert_eq!(
+            last_free_point(&map, player.x, player.y, 100.0, 40.0),
+            (100.0, 40.0)
+        );
+    }
+
+    #[test]
+    fn ignores_non_finite_positions() {
+        let map = SceneMap::from_rows(32.0, &OPEN);
+        let mut player = player_at(100.0, 40.0);
+        let mut budget = MoveBudget::full();
+
+        apply_move(&map, &mut player, &mut budget, f32::NAN, 40.0, "run".into());
+
+        assert_eq!((player.x, player.y), (100.0, 40.0));
+    }
+
+    #[test]
+    fn rejects_a_saved_spot_inside_a_wall() {
+        let map = SceneMap::from_rows(32.0, &WALLED);
+
+        assert_eq!(entry_point(&map, 180.0, 40.0), None);
+        assert_eq!(entry_point(&map, 100.0, 40.0), Some((100.0, 40.0)));
+        assert_eq!(entry_point(&map, -50.0, 40.0), Some((0.0, 40.0)));
+    }
+}
@@ -0,0 +1,418 @@
+use crate::game::aoi::{self, Cell, Grid};
+use crate::game::map::SceneMap;
+use crate::game::movement::{self, MoveBudget};
+use crate::models::game::{GameMessage, Player, PlayerState};
+use crate::models::position::PlayerPosition;
+use crate::repositories::position_repo::PositionRepository;
+use crate::state::ClientSender;
+use axum::extract::ws::Message;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{MissedTickBehavior, interval};
+
+/// One instance of a scene, e.g. CapitalScene #2.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoomId {
+    pub scene: String,
+    pub instance: u32,
+}
+
+impl fmt::Display for RoomId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} #{}", self.scene, self.instance)
+    }
+}
+
+pub enum RoomCommand {
+    Join {
+        player: Player,
+        sender: ClientSender,
+        budget: MoveBudget,
+    },
+    /// Removes a player and hands their state back, e.g. to move them to
+    /// another scene.
+    Leave {
+        id: String,
+        reply: oneshot::Sender<Option<Player>>,
+    },
+    Move {
+        id: String,
+        x: f32,
+        y: f32,
+        anim: String,
+    },
+    /// Sends the player their current position, after a move the session
+    /// refused to forward.
+    Resync {
+        id: String,
+    },
+    /// A message for every member, already serialized.
+    Broadcast(String),
+    Close,
+}
+
+/// Cheap handle to a running room; the room itself lives in its own task.
+#[derive(Clone)]
+pub struct RoomHandle {
+    pub id: RoomId,
+    commands: mpsc::UnboundedSender<RoomCommand>,
+    /// Members plus joins already routed here, kept by the registry so it
+    /// can fill instances without asking the room task.
+    pub(crate) population: Arc<AtomicUsize>,
+}
+
+impl RoomHandle {
+    pub fn send(&self, command: RoomCommand) {
+        // The room only stops once the registry has dropped it
+        let _ = self.commands.send(command);
+    }
+
+    pub async fn leave(&self, id: &str) -> Option<Player> {
+        let (reply, response) = oneshot::channel();
+        self.send(RoomCommand::Leave {
+            id: id.to_string(),
+            reply,
+        });
+        response.await.ok().flatten()
+    }
+}
+
+struct Member {
+    player: Player,
+    sender: ClientSender,
+    budget: MoveBudget,
+    cell: Cell,
+    /// Other members this one has been told about.
+    visible: HashSet<String>,
+}
+
+struct Room {
+    id: RoomId,
+    map: SceneMap,
+    members: HashMap<String, Member>,
+    grid: Grid,
+    /// Members that moved since the last snapshot.
+    dirty: HashSet<String>,
+    /// Members with a wallet that moved since their position was saved.
+    unsaved: HashSet<String>,
+    seq: u64,
+    db: PgPool,
+    population: Arc<AtomicUsize>,
+}
+
+/// Starts the task of a new room. Population starts at zero; the registry
+/// counts joins before sending them.
+pub fn spawn(
+    id: RoomId,
+    map: SceneMap,
+    db: PgPool,
+    tick_rate: u32,
+    save_interval: Duration,
+) -> RoomHandle {
+    let (commands, receiver) = mpsc::unbounded_channel();
+    let population = Arc::new(AtomicUsize::new(0));
+    let room = Room {
+        id: id.clone(),
+        map,
+        members: HashMap::new(),
+        grid: Grid::default(),
+        dirty: HashSet::new(),
+        unsaved: HashSet::new(),
+        seq: 0,
+        db,
+        population: population.clone(),
+    };
+    tokio::spawn(run(room, receiver, tick_rate, save_interval));
+
+    RoomHandle {
+        id,
+        commands,
+        population,
+    }
+}
+
+async fn run(
+    mut room: Room,
+    mut commands: mpsc::UnboundedReceiver<RoomCommand>,
+    tick_rate: u32,
+    save_interval: Duration,
+) {
+    let mut ticker = interval(Duration::from_secs(1) / tick_rate.max(1));
+    // A slow tick delays the next one instead of bursting to catch up
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut saver = interval(save_interval.max(Duration::from_secs(1)));
+    saver.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    println!("Room {} opened", room.id);
+
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(RoomCommand::Close) | None => break,
+                Some(command) => room.handle(command),
+            },
+            _ = ticker.tick() => room.send_snapshot(),
+            _ = saver.tick() => room.save_moved(),
+        }
+    }
+
+    println!("Room {} closed", room.id);
+}
+
+impl Room {
+    fn handle(&mut self, command: RoomCommand) {
+        match command {
+            RoomCommand::Join {
+                player,
+                sender,
+                budget,
+            } => self.join(player, sender, budget),
+            RoomCommand::Leave { id, reply } => {
+                let _ = reply.send(self.leave(&id));
+            }
+            RoomCommand::Move { id, x, y, anim } => self.move_player(&id, x, y, anim),
+            RoomCommand::Resync { id } => {
+                if let Some(member) = self.members.get(&id) {
+                    send(&member.sender, &correction(&member.player));
+                }
+            }
+            RoomCommand::Broadcast(json) => {
+                for member in self.members.values() {
+                    let _ = member.sender.send(Ok(Message::Text(json.clone())));
+                }
+            }
+            RoomCommand::Close => {}
+        }
+    }
+
+    /// Only players within the interest radius are introduced, in both
+    /// directions; the rest meet through `enterView` as they approach.
+    fn join(&mut self, player: Player, sender: ClientSender, budget: MoveBudget) {
+        let id = player.id.clone();
+        let cell = aoi::cell_of(player.x, player.y);
+        let visible = self.grid.nearby(&id, cell);
+        self.grid.insert(&id, cell);
+
+        let new_player_msg = GameMessage::NewPlayer {
+            id: id.clone(),
+            player: player.clone(),
+        };
+        for other in &visible {
+            if let Some(member) = self.members.get_mut(other) {
+                member.visible.insert(id.clone());
+                send(&member.sender, &new_player_msg);
+            }
+        }
+
+        let players = visible
+            .iter()
+            .filter_map(|id| self.members.get(id).map(|member| &member.player))
+            .chain([&player])
+            .map(|player| (player.id.clone(), player.clone()))
+            .collect();
+        self.members.insert(
+            id.clone(),
+            Member {
+                player,
+                sender: sender.clone(),
+                budget,
+                cell,
+                visible,
+            },
+        );
+
+        send(
+            &sender,
+            &GameMessage::RoomJoined {
+                scene: self.id.scene.clone(),
+                instance: self.id.instance,
+            },
+        );
+        send(&sender, &GameMessage::CurrentPlayers { players });
+    }
+
+    fn leave(&mut self, id: &str) -> Option<Player> {
+        let member = self.members.remove(id)?;
+        self.population.fetch_sub(1, Ordering::SeqCst);
+        self.dirty.remove(id);
+        self.unsaved.remove(id);
+        self.grid.remove(id, member.cell);
+        self.save_positions([&member.player]);
+
+        let disconnect_msg = GameMessage::UserDisconnected { id: id.to_string() };
+        for other in &member.visible {
+            if let Some(other) = self.members.get_mut(other) {
+                other.visible.remove(id);
+                send(&other.sender, &disconnect_msg);
+            }
+        }
+        Some(member.player)
+    }
+
+    fn move_player(&mut self, id: &str, x: f32, y: f32, anim: String) {
+        let Some(member) = self.members.get_mut(id) else {
+            return;
+        };
+        let corrected = movement::apply_move(
+            &self.map,
+            &mut member.player,
+            &mut member.budget,
+            x,
+            y,
+            anim,
+        );
+        if corrected.is_some() {
+            send(&member.sender, &correction(&member.player));
+        }
+        if member.player.wallet.is_some() {
+            self.unsaved.insert(id.to_string());
+        }
+
+        let cell = aoi::cell_of(member.player.x, member.player.y);
+        if cell != member.cell {
+            self.grid.relocate(id, member.cell, cell);
+            member.cell = cell;
+            self.update_view(id);
+        }
+        // Sent to the players in view with the next snapshot
+        self.dirty.insert(id.to_string());
+    }
+
+    fn save_moved(&mut self) {
+        let unsaved = std::mem::take(&mut self.unsaved);
+        self.save_positions(
+            unsaved
+                .iter()
+                .filter_map(|id| self.members.get(id))
+                .map(|member| &member.player),
+        );
+    }
+
+    /// Stores where wallet players are, in the background so the room keeps
+    /// ticking while the database is slow.
+    fn save_positions<'a>(&self, players: impl IntoIterator<Item = &'a Player>) {
+        let positions: Vec<PlayerPosition> = players
+            .into_iter()
+            .filter_map(|player| {
+                Some(PlayerPosition {
+                    wallet_address: player.wallet.clone()?,
+                    scene: player.scene.clone(),
+                    x: player.x,
+                    y: player.y,
+                    anim: player.anim.clone(),
+                })
+            })
+            .collect();
+        if positions.is_empty() {
+            return;
+        }
+
+        let positions_repo = PositionRepository::new(self.db.clone());
+        tokio::spawn(async move {
+            if let Err(e) = positions_repo.save_positions(&positions).await {
+                eprintln!("Failed to save player positions: {:?}", e);
+            }
+        });
+    }
+
+    /// After a member changed cells, introduces the players that came into
+    /// view and drops the ones that went out of it, on both sides.
+    fn update_view(&mut self, id: &str) {
+        let Some(member) = self.members.get(id) else {
+            return;
+        };
+        let now = self.grid.nearby(id, member.cell);
+        let entered: Vec<String> = now.difference(&member.visible).cloned().collect();
+        let left: Vec<String> = member.visible.difference(&now).cloned().collect();
+        let mover = member.player.clone();
+        let mover_sender = member.sender.clone();
+
+        let leave_msg = GameMessage::LeaveView { id: id.to_string() };
+        for other_id in &left {
+            if let Some(other) = self.members.get_mut(other_id) {
+                other.visible.remove(id);
+                send(&other.sender, &leave_msg);
+            }
+            send(
+                &mover_sender,
+                &GameMessage::LeaveView {
+                    id: other_id.clone(),
+                },
+            );
+        }
+
+        let enter_msg = GameMessage::EnterView {
+            player: mover.clone(),
+        };
+        for other_id in &entered {
+            if let Some(other) = self.members.get_mut(other_id) {
+                other.visible.insert(id.to_string());
+                send(&other.sender, &enter_msg);
+                send(
+                    &mover_sender,
+                    &GameMessage::EnterView {
+                        player: other.player.clone(),
+                    },
+                );
+            }
+        }
+
+        if let Some(member) = self.members.get_mut(id) {
+            member.visible = now;
+        }
+    }
+
+    /// Sends each member the moved players they can see. `seq` advances
+    /// once per tick, so a member may skip numbers when nothing near them
+    /// moved.
+    fn send_snapshot(&mut self) {
+        if self.dirty.is_empty() {
+            return;
+        }
+        self.seq += 1;
+
+        let moved: Vec<(String, PlayerState)> = self
+            .dirty
+            .drain()
+            .filter_map(|id| {
+                let state = PlayerState::from(&self.members.get(&id)?.player);
+                Some((id, state))
+            })
+            .collect();
+        for member in self.members.values() {
+            let players: Vec<PlayerState> = moved
+                .iter()
+                .filter(|(id, _)| member.visible.contains(id))
+                .map(|(_, state)| state.clone())
+                .collect();
+            if players.is_empty() {
+                continue;
+            }
+
+            let snapshot = GameMessage::Snapshot {
+                seq: self.seq,
+                scene: self.id.scene.clone(),
+                players,
+            };
+            send(&member.sender, &snapshot);
+        }
+    }
+}
+
+fn correction(player: &Player) -> GameMessage {
+    GameMessage::Correction {
+        x: player.x,
+        y: player.y,
+        scene: player.scene.clone(),
+    }
+}
+
+pub(crate) fn send(sender: &ClientSender, msg: &GameMessage) {
+    if let Ok(json) = serde_json::to_string(msg) {
+        let _ = sender.send(Ok(Message::Text(json)));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::game::map::Maps;
+use crate::game::movement::MoveBudget;
+use crate::game::room::{self, RoomCommand, RoomHandle, RoomId};
+use crate::models::game::{GameMessage, Player};
+use crate::state::ClientSender;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Registry of the running rooms, with the instances of each scene.
+#[derive(Clone)]
+pub struct Rooms {
+    maps: Arc<Maps>,
+    db: PgPool,
+    /// Players per instance before another instance of the scene is opened.
+    capacity: usize,
+    tick_rate: u32,
+    /// How often rooms store the positions of players with a wallet.
+    save_interval: Duration,
+    scenes: Arc<Mutex<HashMap<String, Vec<RoomHandle>>>>,
+}
+
+impl Rooms {
+    pub fn new(
+        maps: Arc<Maps>,
+        db: PgPool,
+        capacity: usize,
+        tick_rate: u32,
+        save_interval: Duration,
+    ) -> Self {
+        Self {
+            maps,
+            db,
+            capacity: capacity.max(1),
+            tick_rate,
+            save_interval,
+            scenes: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Puts the player into the first instance of their scene that has room,
+    /// opening a new instance when all are full. Returns `None` for scenes
+    /// the server has no map for.
+    pub fn join(
+        &self,
+        player: Player,
+        sender: ClientSender,
+        budget: MoveBudget,
+    ) -> Option<RoomHandle> {
+        let map = self.maps.get(&player.scene)?;
+        let mut scenes = self.scenes.lock().unwrap();
+        let instances = scenes.entry(player.scene.clone()).or_default();
+
+        let room = match instances
+            .iter()
+            .find(|room| room.population.load(Ordering::SeqCst) < self.capacity)
+        {
+            Some(room) => room.clone(),
+            None => {
+                // Reuse the lowest instance number that was closed
+                let instance = (1..)
+                    .find(|n| instances.iter().all(|room| room.id.instance != *n))
+                    .unwrap_or(1);
+                let id = RoomId {
+                    scene: player.scene.clone(),
+                    instance,
+                };
+                let room = room::spawn(
+                    id,
+                    map.clone(),
+                    self.db.clone(),
+                    self.tick_rate,
+                    self.save_interval,
+                );
+                instances.push(room.clone());
+                instances.sort_by_key(|room| room.id.instance);
+                room
+            }
+        };
+
+        room.population.fetch_add(1, Ordering::SeqCst);
+        room.send(RoomCommand::Join {
+            player,
+            sender,
+            budget,
+        });
+        Some(room)
+    }
+
+    /// Called after a player left `room`; closes it once nobody is left.
+    /// Joins count towards the population under the same lock, so a room
+    /// that is about to receive a player is never closed.
+    pub fn release(&self, room: &RoomHandle) {
+        if room.population.load(Ordering::SeqCst) > 0 {
+            return;
+        }
+
+        let mut scenes = self.scenes.lock().unwrap();
+        if room.population.load(Ordering::SeqCst) > 0 {
+            return;
+        }
+        if let Some(instances) = scenes.get_mut(&room.id.scene) {
+            instances.retain(|other| other.id != room.id);
+            if instances.is_empty() {
+                scenes.remove(&room.id.scene);
+            }
+        }
+        room.send(RoomCommand::Close);
+    }
+
+    /// Sends a message to every player in every room.
+    pub fn broadcast(&self, msg: &GameMessage) {
+        let Ok(json) = serde_json::to_string(msg) else {
+            return;
+        };
+        for instances in self.scenes.lock().unwrap().values() {
+            for room in instances {
+                room.send(RoomCommand::Broadcast(json.clone()));
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::game::chat::ChatHub;
+use crate::game::room::RoomHandle;
+use crate::game::rooms::Rooms;
+use crate::state::ClientSender;
+use axum::extract::ws::Message;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::{Instant, sleep_until};
+
+/// Receiving end of a client's outgoing messages. It outlives the socket,
+/// so whatever the room sends during a reconnect is delivered afterwards.
+pub type Outbox = mpsc::UnboundedReceiver<Result<Message, axum::Error>>;
+
+/// A connected player, independent of the socket currently serving them.
+pub struct Session {
+    pub id: String,
+    pub wallet: Option<String>,
+    /// Message protocol negotiated when the session started.
+    pub protocol: u8,
+    pub resume_token: String,
+    pub room: RoomHandle,
+    pub sender: ClientSender,
+    pub outbox: Outbox,
+}
+
+/// Sessions whose socket dropped. The player stays in their room until the
+/// client resumes with the token from `welcome` or the grace period ends.
+#[derive(Clone)]
+pub struct Sessions {
+    rooms: Rooms,
+    chat: ChatHub,
+    grace: Duration,
+    suspended: Arc<Mutex<HashMap<String, (Instant, Session)>>>,
+}
+
+impl Sessions {
+    pub fn new(rooms: Rooms, chat: ChatHub, grace: Duration) -> Self {
+        Self {
+            rooms,
+            chat,
+            grace,
+            suspended: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn suspend(&self, session: Session) {
+        let token = session.resume_token.clone();
+        let expires_at = Instant::now() + self.grace;
+        self.suspended
+            .lock()
+            .unwrap()
+            .insert(token.clone(), (expires_at, session));
+
+        let sessions = self.clone();
+        tokio::spawn(async move {
+            sleep_until(expires_at).await;
+            sessions.expire(&token).await;
+        });
+    }
+
+    /// Takes a suspended session back. The token only resumes a session of
+    /// the same wallet, or a guest session for a guest.
+    pub fn resume(&self, token: &str, wallet: &Option<String>) -> Option<Session> {
+        let mut suspended = self.suspended.lock().unwrap();
+        if suspended.get(token)?.1.wallet != *wallet {
+            return None;
+        }
+        suspended.remove(token).map(|(_, session)| session)
+    }
+
+    async fn expire(&self, token: &str) {
+        // A session that was resumed and dropped again has a later deadline
+        let expired = {
+            let mut suspended = self.suspended.lock().unwrap();
+            match suspended.get(token) {
+                Some((expires_at, _)) if *expires_at <= Instant::now() => suspended.remove(token),
+                _ => None,
+            }
+        };
+
+        if let Some((_, session)) = expired {
+            session.room.leave(&session.id).await;
+            self.rooms.release(&session.room);
+            self.chat.disconnect(&session.id);
+            println!("{} disconnected (not resumed)", session.id);
+        }
+    }
+}
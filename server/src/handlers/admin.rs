@@ -0,0 +1,116 @@
+use crate::error::AppError;
+use crate::models::moderation::{Sanction, SanctionKind};
+use crate::repositories::moderation_repo::ModerationRepository;
+use crate::state::AppState;
+use crate::utils::address::checksummed;
+use crate::utils::jwt;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct SanctionsResponse {
+    pub sanctions: Vec<Sanction>,
+}
+
+#[derive(Deserialize)]
+pub struct SanctionRequest {
+    pub reason: Option<String>,
+    /// Seconds until it expires; permanent when omitted.
+    pub duration_secs: Option<i64>,
+}
+
+pub async fn list_sanctions(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> Result<Json<SanctionsResponse>, AppError> {
+    require_admin(&headers, &state)?;
+
+    let sanctions = ModerationRepository::new(state.db.clone())
+        .list_active()
+        .await?;
+
+    Ok(Json(SanctionsResponse { sanctions }))
+}
+
+/// Mutes or bans a wallet. It takes effect right away for connected
+/// sessions: a mute stops their chat, a ban closes their connections.
+pub async fn set_sanction(
+    headers: HeaderMap,
+    Path((wallet, kind)): Path<(String, String)>,
+    State(state): State<AppState>,
+    Json(request): Json<SanctionRequest>,
+) -> Result<Json<Sanction>, AppError> {
+    let admin = require_admin(&headers, &state)?;
+    let wallet = checksummed(&wallet)?;
+    let kind = parse_kind(&kind)?;
+    let expires_at = match request.duration_secs {
+        Some(secs) if secs <= 0 => {
+            return Err(AppError::BadRequest(format!("invalid duration: {}", secs)));
+        }
+        Some(secs) => Some(chrono::Utc::now() + chrono::Duration::seconds(secs)),
+        None => None,
+    };
+
+    let sanction = ModerationRepository::new(state.db.clone())
+        .upsert_sanction(&wallet, kind, request.reason.as_deref(), &admin, expires_at)
+        .await?;
+    match kind {
+        SanctionKind::Mute => state.chat.set_mute(&wallet, Some(sanction.clone())),
+        SanctionKind::Ban => state.chat.close_wallet(&wallet, &sanction),
+    }
+
+    Ok(Json(sanction))
+}
+
+pub async fn lift_sanction(
+    headers: HeaderMap,
+    Path((wallet, kind)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, AppError> {
+    require_admin(&headers, &state)?;
+    let wallet = checksummed(&wallet)?;
+    let kind = parse_kind(&kind)?;
+
+    let lifted = ModerationRepository::new(state.db.clone())
+        .delete_sanction(&wallet, kind)
+        .await?;
+    match kind {
+        SanctionKind::Mute => state.chat.set_mute(&wallet, None),
+        SanctionKind::Ban => state.chat.lift_ban(&wallet),
+    }
+
+    Ok(if lifted {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    })
+}
+
+/// Accepts a Sign-In with Ethereum session token, sent as a bearer token,
+/// of a wallet listed in `ADMIN_WALLETS`. Returns that wallet.
+fn require_admin(headers: &HeaderMap, state: &AppState) -> Result<String, AppError> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| AppError::Unauthorized("missing bearer token".to_string()))?;
+    let claims = jwt::verify_token(token, &state.config.jwt_secret)
+        .map_err(|e| AppError::Unauthorized(e.to_string()))?;
+    if !state.config.admin_wallets.contains(&claims.sub) {
+        return Err(AppError::Forbidden(format!(
+            "{} is not an admin",
+            claims.sub
+        )));
+    }
+
+    Ok(claims.sub)
+}
+
+fn parse_kind(kind: &str) -> Result<SanctionKind, AppError> {
+    kind.parse()
+        .map_err(|e: anyhow::Error| AppError::BadRequest(e.to_string()))
+}
@@ -0,0 +1,68 @@
+use crate::error::AppError;
+use crate::state::AppState;
+use crate::utils::{jwt, siwe};
+use axum::{Json, extract::State};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// How long an issued nonce can be used to sign in.
+const NONCE_TTL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Serialize)]
+pub struct NonceResponse {
+    pub nonce: String,
+}
+
+#[derive(Deserialize)]
+pub struct VerifyRequest {
+    /// The EIP-4361 message exactly as the wallet signed it.
+    pub message: String,
+    pub signature: String,
+}
+
+#[derive(Serialize)]
+pub struct VerifyResponse {
+    pub token: String,
+    pub wallet: String,
+}
+
+/// Issues a single-use nonce for the client to embed in its sign-in message.
+pub async fn nonce(State(state): State<AppState>) -> Json<NonceResponse> {
+    let nonce = Uuid::new_v4().simple().to_string();
+
+    let mut nonces = state.nonces.lock().unwrap();
+    nonces.retain(|_, issued| issued.elapsed() < NONCE_TTL);
+    nonces.insert(nonce.clone(), Instant::now());
+
+    Json(NonceResponse { nonce })
+}
+
+/// Verifies a signed sign-in message and returns a session token for the
+/// wallet that signed it.
+pub async fn verify(
+    State(state): State<AppState>,
+    Json(request): Json<VerifyRequest>,
+) -> Result<Json<VerifyResponse>, AppError> {
+    let message = siwe::verify(
+        &request.message,
+        &request.signature,
+        &state.config.siwe_domain,
+        state.config.chain_id,
+    )
+    .map_err(|e| AppError::Unauthorized(e.to_string()))?;
+
+    // Nonces are consumed even if they turn out to be stale
+    let issued = state.nonces.lock().unwrap().remove(&message.nonce);
+    if issued.is_none_or(|issued| issued.elapsed() >= NONCE_TTL) {
+        return Err(AppError::Unauthorized(
+            "unknown or expired nonce".to_string(),
+        ));
+    }
+
+    let wallet = message.address.to_string();
+    let token = jwt::generate_token(&wallet, &state.config.jwt_secret)?;
+    println!("{} signed in", wallet);
+
+    Ok(Json(VerifyResponse { token, wallet }))
+}
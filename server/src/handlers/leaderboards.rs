@@ -0,0 +1,52 @@
+use crate::error::AppError;
+use crate::models::leaderboard::{ALL_TIME, Leaderboard, LeaderboardEntry};
+use crate::repositories::leaderboard_repo::LeaderboardRepository;
+use crate::state::AppState;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 1000;
+
+#[derive(Deserialize)]
+pub struct LeaderboardParams {
+    /// Rank a single epoch; all-time when omitted.
+    pub epoch: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct LeaderboardResponse {
+    pub board: Leaderboard,
+    pub epoch: Option<i64>,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+pub async fn get_leaderboard(
+    Path(board): Path<String>,
+    Query(params): Query<LeaderboardParams>,
+    State(state): State<AppState>,
+) -> Result<Json<LeaderboardResponse>, AppError> {
+    let board: Leaderboard = board
+        .parse()
+        .map_err(|e: anyhow::Error| AppError::BadRequest(e.to_string()))?;
+    if let Some(epoch) = params.epoch
+        && epoch < 0
+    {
+        return Err(AppError::BadRequest(format!("invalid epoch: {}", epoch)));
+    }
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let entries = LeaderboardRepository::new(state.db.clone())
+        .find_entries(board, params.epoch.unwrap_or(ALL_TIME), limit)
+        .await?;
+
+    Ok(Json(LeaderboardResponse {
+        board,
+        epoch: params.epoch,
+        entries,
+    }))
+}
@@ -1 +1,6 @@
+pub mod admin;
+pub mod auth;
+pub mod leaderboards;
+pub mod players;
+pub mod regions;
 pub mod ws;
@@ -0,0 +1,77 @@
+use crate::error::AppError;
+use crate::models::farm::FarmPosition;
+use crate::models::land::LandToken;
+use crate::models::store::InventoryItem;
+use crate::models::ve::VeLock;
+use crate::models::voter::{BribeClaim, Vote};
+use crate::repositories::farm_repo::FarmRepository;
+use crate::repositories::land_repo::LandRepository;
+use crate::repositories::store_repo::StoreRepository;
+use crate::repositories::token_repo::TokenRepository;
+use crate::repositories::ve_repo::VeRepository;
+use crate::repositories::voter_repo::VoterRepository;
+use crate::state::AppState;
+use crate::utils::address::checksummed;
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct PlayerProfile {
+    pub wallet: String,
+    pub olig_balance: String,
+    pub meth_balance: String,
+    pub farm_positions: Vec<FarmPosition>,
+    pub ve_lock: Option<VeLock>,
+    /// veOLIG balance right now; decays towards the lock's unlock time.
+    pub voting_power: String,
+    pub land: Vec<LandToken>,
+    /// Vote boost percent of the wallet's best land tier, as applied by the voter.
+    pub best_multiplier: i64,
+    pub items: Vec<InventoryItem>,
+    pub votes: Vec<Vote>,
+    pub bribe_claims: Vec<BribeClaim>,
+}
+
+/// Everything the indexer knows about one wallet.
+pub async fn get_player(
+    Path(wallet): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<PlayerProfile>, AppError> {
+    let wallet = checksummed(&wallet)?;
+    let olig_token = checksummed(&state.config.OLIG_TOKEN_ADDRESS)?;
+    let meth_token = checksummed(&state.config.MOCK_MANTLE_ADDRESS)?;
+
+    let ve_lock = VeRepository::new(state.db.clone())
+        .find_lock(&wallet)
+        .await?;
+    let now = chrono::Utc::now().timestamp() as u64;
+    let voting_power = match &ve_lock {
+        Some(lock) => lock.voting_power_at(now)?.to_string(),
+        None => "0".to_string(),
+    };
+
+    let tokens = TokenRepository::new(state.db.clone());
+    let land_repo = LandRepository::new(state.db.clone());
+    let voter_repo = VoterRepository::new(state.db.clone());
+
+    Ok(Json(PlayerProfile {
+        olig_balance: tokens.find_balance(&olig_token, &wallet).await?,
+        meth_balance: tokens.find_balance(&meth_token, &wallet).await?,
+        farm_positions: FarmRepository::new(state.db.clone())
+            .find_positions(&wallet)
+            .await?,
+        ve_lock,
+        voting_power,
+        land: land_repo.find_tokens_by_owner(&wallet).await?,
+        best_multiplier: land_repo.best_multiplier(&wallet).await?,
+        items: StoreRepository::new(state.db.clone())
+            .find_inventory(&wallet)
+            .await?,
+        votes: voter_repo.find_votes_by_voter(&wallet).await?,
+        bribe_claims: voter_repo.find_bribe_claims_by_voter(&wallet).await?,
+        wallet,
+    }))
+}
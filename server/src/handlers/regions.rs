@@ -0,0 +1,120 @@
+use crate::error::AppError;
+use crate::models::politics::{Governor, Revolution, RevolutionOutcome};
+use crate::models::war::War;
+use crate::repositories::farm_repo::FarmRepository;
+use crate::repositories::politics_repo::PoliticsRepository;
+use crate::repositories::region_repo::RegionRepository;
+use crate::repositories::voter_repo::VoterRepository;
+use crate::repositories::war_repo::WarRepository;
+use crate::state::AppState;
+use crate::utils::epoch::epoch_at;
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct RegionsResponse {
+    /// Current epoch, computed like `OligarchyVoter.getCurrentEpoch`.
+    pub epoch: i64,
+    pub regions: Vec<RegionEpoch>,
+}
+
+/// State of one region during one epoch.
+#[derive(Serialize)]
+pub struct RegionEpoch {
+    pub region_id: i64,
+    pub epoch: i64,
+    pub vote_weight: String,
+    pub bribe_pot: String,
+    /// Current farm TVL of the region's pool; stakes are not kept per epoch.
+    pub tvl: String,
+    /// Elected in the previous epoch; `ousted` is set if a revolution succeeded.
+    pub governor: Option<Governor>,
+    pub revolution: Option<RevolutionStatus>,
+    /// Wars the region attacks or defends in this epoch.
+    pub wars: Vec<War>,
+}
+
+#[derive(Serialize)]
+pub struct RevolutionStatus {
+    #[serde(flatten)]
+    pub revolution: Revolution,
+    pub outcome: RevolutionOutcome,
+}
+
+pub async fn list_regions(
+    State(state): State<AppState>,
+) -> Result<Json<RegionsResponse>, AppError> {
+    let epoch = epoch_at(now()) as i64;
+
+    let mut regions = Vec::new();
+    for region_id in RegionRepository::new(state.db.clone())
+        .find_region_ids()
+        .await?
+    {
+        regions.push(region_epoch(&state, region_id, epoch).await?);
+    }
+
+    Ok(Json(RegionsResponse { epoch, regions }))
+}
+
+pub async fn get_region_epoch(
+    Path((region_id, epoch)): Path<(i64, i64)>,
+    State(state): State<AppState>,
+) -> Result<Json<RegionEpoch>, AppError> {
+    if epoch < 0 {
+        return Err(AppError::BadRequest(format!("invalid epoch: {}", epoch)));
+    }
+    // Same set `list_regions` reports, so an unknown id is not served as all zeros
+    if !RegionRepository::new(state.db.clone())
+        .find_region_ids()
+        .await?
+        .contains(&region_id)
+    {
+        return Err(AppError::NotFound(format!("unknown region: {}", region_id)));
+    }
+
+    Ok(Json(region_epoch(&state, region_id, epoch).await?))
+}
+
+async fn region_epoch(
+    state: &AppState,
+    region_id: i64,
+    epoch: i64,
+) -> Result<RegionEpoch, AppError> {
+    let voter_repo = VoterRepository::new(state.db.clone());
+    let politics_repo = PoliticsRepository::new(state.db.clone());
+
+    let revolution = politics_repo
+        .find_revolution(region_id, epoch)
+        .await?
+        .map(|revolution| RevolutionStatus {
+            outcome: revolution.outcome(now()),
+            revolution,
+        });
+    let wars = WarRepository::new(state.db.clone())
+        .find_wars_by_epoch(epoch)
+        .await?
+        .into_iter()
+        .filter(|war| war.attacker_region == region_id || war.defender_region == region_id)
+        .collect();
+
+    Ok(RegionEpoch {
+        region_id,
+        epoch,
+        vote_weight: voter_repo.vote_weight(epoch, region_id).await?,
+        bribe_pot: voter_repo.bribe_pot(epoch, region_id).await?,
+        tvl: FarmRepository::new(state.db.clone())
+            .pool_tvl(region_id)
+            .await?,
+        governor: politics_repo.find_ruling_governor(region_id, epoch).await?,
+        revolution,
+        wars,
+    })
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
@@ -1,72 +1,162 @@
+use crate::error::AppError;
+use crate::events::DomainEvent;
+use crate::game::chat::{LEGACY_PROTOCOL, PROTOCOL_VERSION};
+use crate::game::movement::{self, MoveBudget};
+use crate::game::room::{self, RoomCommand, RoomHandle};
+use crate::game::sessions::{Outbox, Session};
+use crate::models::chat::{self, GLOBAL_CHANNEL, MemberChannel};
 use crate::models::game::{GameMessage, Player};
-use crate::state::{AppState, Clients, Players};
+use crate::models::moderation::{Sanction, SanctionKind};
+use crate::models::position::PlayerPosition;
+use crate::models::store::InventoryItem;
+use crate::repositories::moderation_repo::ModerationRepository;
+use crate::repositories::politics_repo::PoliticsRepository;
+use crate::repositories::position_repo::PositionRepository;
+use crate::repositories::region_repo::RegionRepository;
+use crate::repositories::store_repo::StoreRepository;
+use crate::state::{AppState, ClientSender};
+use crate::utils::jwt;
+use alloy::primitives::Address;
 use axum::{
-    extract::{ws::{Message, WebSocket, WebSocketUpgrade}, State},
+    extract::{
+        ConnectInfo, Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
     response::IntoResponse,
 };
-use futures::{FutureExt, StreamExt};
-use tokio::sync::mpsc;
+use futures::{SinkExt, StreamExt, stream::SplitSink};
+use serde::Deserialize;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use uuid::Uuid;
 
+/// Scene new players start in.
+const SPAWN_SCENE: &str = "CapitalScene";
+/// Shortest time between two scene changes of a session.
+const SCENE_CHANGE_COOLDOWN: Duration = Duration::from_secs(2);
+
+#[derive(Deserialize)]
+pub struct WsParams {
+    /// Session token from Sign-In with Ethereum. Browsers cannot set headers
+    /// on a WebSocket handshake, so it travels in the query string.
+    token: Option<String>,
+    /// Resume token from an earlier `welcome`, to pick up a dropped session.
+    resume: Option<String>,
+    /// Message protocol the client speaks; clients that predate chat
+    /// channels send none and get protocol 1.
+    protocol: Option<u8>,
+}
+
+/// Connections without a token join as guests; with one, the session is
+/// bound to the wallet that signed in. Banned wallets are turned away, and
+/// so are guests at an address a banned wallet connected from.
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    Query(params): Query<WsParams>,
     State(state): State<AppState>,
-) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_socket(socket, state))
-}
-
-async fn handle_socket(ws: WebSocket, state: AppState) {
-    let (client_ws_sender, mut client_ws_rcv) = ws.split();
-    let (client_sender, client_rcv) = mpsc::unbounded_channel();
-
-    let client_rcv = tokio_stream::wrappers::UnboundedReceiverStream::new(client_rcv);
-    tokio::task::spawn(client_rcv.forward(client_ws_sender).map(|result| {
-        if let Err(e) = result {
-            eprintln!("error sending websocket msg: {}", e);
+) -> Result<impl IntoResponse, AppError> {
+    let address = peer.ip();
+    let wallet = match params.token {
+        Some(token) => {
+            let claims = jwt::verify_token(&token, &state.config.jwt_secret)
+                .map_err(|e| AppError::Unauthorized(e.to_string()))?;
+            Some(claims.sub)
         }
-    }));
-
-    let id = Uuid::new_v4().to_string();
-    let new_player = Player {
-        id: id.clone(),
-        x: 640.0,
-        y: 360.0,
-        anim: "idle-down".to_string(),
-        scene: "CapitalScene".to_string(),
+        None => None,
     };
-
+    let sanctions = match &wallet {
+        Some(wallet) => load_sanctions(&state, wallet).await,
+        None => Vec::new(),
+    };
+    let (bans, mut mutes): (Vec<_>, Vec<_>) = sanctions
+        .into_iter()
+        .partition(|sanction| sanction.kind == SanctionKind::Ban.as_str());
+    if let Some(ban) = bans.first() {
+        state.chat.ban_address(address, ban);
+        return Err(AppError::Forbidden(ban.describe()));
+    }
+    if wallet.is_none()
+        && let Some(ban) = state.chat.address_ban(address)
     {
-        state.clients.lock().unwrap().insert(id.clone(), client_sender);
-        state.players
-            .lock()
-            .unwrap()
-            .insert(id.clone(), new_player.clone());
+        return Err(AppError::Forbidden(ban.describe()));
     }
 
-    println!("{} connected", id);
+    let protocol = params
+        .protocol
+        .unwrap_or(LEGACY_PROTOCOL)
+        .clamp(LEGACY_PROTOCOL, PROTOCOL_VERSION);
 
-    let welcome_msg = GameMessage::Welcome { id: id.clone() };
-    if let Ok(json) = serde_json::to_string(&welcome_msg) {
-        if let Some(sender) = state.clients.lock().unwrap().get(&id) {
-            let _ = sender.send(Ok(Message::Text(json)));
-        }
-    }
+    let mute = mutes.pop();
+    Ok(ws.on_upgrade(move |socket| {
+        handle_socket(
+            socket,
+            state,
+            wallet,
+            address,
+            params.resume,
+            protocol,
+            mute,
+        )
+    }))
+}
 
-    let current_players_msg = GameMessage::CurrentPlayers {
-        players: state.players.lock().unwrap().clone(),
-    };
-    if let Ok(json) = serde_json::to_string(&current_players_msg) {
-        if let Some(sender) = state.clients.lock().unwrap().get(&id) {
-            let _ = sender.send(Ok(Message::Text(json)));
-        }
-    }
+async fn handle_socket(
+    ws: WebSocket,
+    state: AppState,
+    wallet: Option<String>,
+    address: IpAddr,
+    resume: Option<String>,
+    protocol: u8,
+    mute: Option<Sanction>,
+) {
+    let (client_ws_sender, mut client_ws_rcv) = ws.split();
 
-    let new_player_msg = GameMessage::NewPlayer {
-        id: id.clone(),
-        player: new_player.clone(),
+    let resumed = resume
+        .as_deref()
+        .and_then(|token| state.sessions.resume(token, &wallet));
+    let is_resumed = resumed.is_some();
+    let session = match resumed {
+        Some(session) => session,
+        None => match start_session(&state, wallet, address, protocol, mute).await {
+            Some(session) => session,
+            None => return,
+        },
     };
-    broadcast_message(&new_player_msg, &id, &state.clients, &state.players, Some(&new_player.scene));
+    let Session {
+        id,
+        wallet,
+        protocol,
+        resume_token,
+        mut room,
+        sender: client_sender,
+        outbox,
+    } = session;
 
+    // Messages queued while the previous socket was gone go out first
+    let (stop_writer, writer_stopped) = oneshot::channel();
+    let writer = tokio::spawn(write_outbox(client_ws_sender, outbox, writer_stopped));
+
+    if is_resumed {
+        println!("{} resumed (wallet: {:?})", id, wallet);
+        let welcome_msg = GameMessage::Welcome {
+            id: id.clone(),
+            wallet: wallet.clone(),
+            resume_token: resume_token.clone(),
+            resumed: true,
+            protocol,
+        };
+        room::send(&client_sender, &welcome_msg);
+        // Puts the client back where the server kept the player
+        room.send(RoomCommand::Resync { id: id.clone() });
+    } else {
+        println!("{} connected (wallet: {:?})", id, wallet);
+    }
+
+    let mut last_scene_change: Option<Instant> = None;
+    let mut closed = false;
     while let Some(result) = client_ws_rcv.next().await {
         let msg = match result {
             Ok(msg) => msg,
@@ -75,45 +165,83 @@ async fn handle_socket(ws: WebSocket, state: AppState) {
                 break;
             }
         };
+        if let Message::Close(_) = msg {
+            closed = true;
+            break;
+        }
 
         if let Ok(text) = msg.to_text() {
             match serde_json::from_str::<GameMessage>(text) {
-                Ok(parsed) => {
-                    match parsed {
-                        GameMessage::Move { x, y, anim, scene } => {
-                            if let Some(player) = state.players.lock().unwrap().get_mut(&id) {
-                                player.x = x;
-                                player.y = y;
-                                player.anim = anim.clone();
-                                player.scene = scene.clone();
-                            }
-
-                            let move_msg = GameMessage::PlayerMoved {
+                Ok(parsed) => match parsed {
+                    GameMessage::Move { x, y, anim, scene } => {
+                        let cooling_down = last_scene_change
+                            .is_some_and(|changed| changed.elapsed() < SCENE_CHANGE_COOLDOWN);
+                        if scene == room.id.scene {
+                            room.send(RoomCommand::Move {
                                 id: id.clone(),
                                 x,
                                 y,
                                 anim,
-                                scene: scene.clone(),
-                            };
-                            broadcast_message(&move_msg, &id, &state.clients, &state.players, Some(&scene));
+                            });
+                        } else if !cooling_down
+                            && let Some(next) =
+                                change_room(&state, &room, &id, &client_sender, anim, scene).await
+                        {
+                            last_scene_change = Some(Instant::now());
+                            state.chat.leave(&id, &chat::scene_channel(&room.id.scene));
+                            state
+                                .chat
+                                .join(&id, &chat::scene_channel(&next.id.scene))
+                                .await;
+                            room = next;
+                        } else {
+                            room.send(RoomCommand::Resync { id: id.clone() });
                         }
-                        GameMessage::Chat { message, .. } => {
-                            let scene = {
-                                let p = state.players.lock().unwrap();
-                                p.get(&id).map(|p| p.scene.clone())
-                            };
-
-                            if let Some(scene) = scene {
-                                let chat_msg = GameMessage::Chat {
-                                    id: id.clone(),
-                                    message,
-                                };
-                                broadcast_message(&chat_msg, &id, &state.clients, &state.players, Some(&scene));
-                            }
+                    }
+                    GameMessage::Chat { message, .. } => {
+                        let channel = chat::scene_channel(&room.id.scene);
+                        if let Err(e) = state.chat.publish(&id, &channel, &message) {
+                            reject_chat(&client_sender, e.to_string());
                         }
-                        _ => {}
                     }
-                }
+                    GameMessage::ChatSend { channel, message } => {
+                        let channel = match chat::joinable_channel(&channel) {
+                            _ if channel == "scene" => chat::scene_channel(&room.id.scene),
+                            Ok(member_channel) => member_channel.name(),
+                            Err(_) => channel,
+                        };
+                        if let Err(e) = state.chat.publish(&id, &channel, &message) {
+                            reject_chat(&client_sender, e.to_string());
+                        }
+                    }
+                    GameMessage::ChatJoin { channel } => match chat::joinable_channel(&channel) {
+                        Ok(channel) if is_member(&state, wallet.as_deref(), &channel).await => {
+                            state.chat.join(&id, &channel.name()).await;
+                        }
+                        Ok(channel) => {
+                            reject_chat(
+                                &client_sender,
+                                format!("not a member of {}", channel.name()),
+                            );
+                        }
+                        Err(e) => reject_chat(&client_sender, e.to_string()),
+                    },
+                    // Global and scene chat follow the session, not the client
+                    GameMessage::ChatLeave { channel }
+                        if channel != GLOBAL_CHANNEL && !channel.starts_with("scene:") =>
+                    {
+                        state.chat.leave(&id, &channel);
+                    }
+                    GameMessage::Whisper { to, message } => match Address::from_str(&to) {
+                        Ok(to) => {
+                            if let Err(e) = state.chat.whisper(&id, to.to_string(), &message) {
+                                reject_chat(&client_sender, e.to_string());
+                            }
+                        }
+                        Err(_) => reject_chat(&client_sender, format!("invalid address: {}", to)),
+                    },
+                    _ => {}
+                },
                 Err(e) => {
                     eprintln!("Failed to parse message: {} - Error: {}", text, e);
                 }
@@ -121,47 +249,267 @@ async fn handle_socket(ws: WebSocket, state: AppState) {
         }
     }
 
-    let scene = {
-        state.players.lock().unwrap().get(&id).map(|p| p.scene.clone())
+    let _ = stop_writer.send(());
+    match writer.await {
+        // A dropped connection may come back; a client that closed it won't
+        Ok(outbox) if !closed => {
+            println!("{} dropped, keeping the session for a reconnect", id);
+            state.sessions.suspend(Session {
+                id,
+                wallet,
+                protocol,
+                resume_token,
+                room,
+                sender: client_sender,
+                outbox,
+            });
+        }
+        _ => {
+            room.leave(&id).await;
+            state.rooms.release(&room);
+            state.chat.disconnect(&id);
+            println!("{} disconnected", id);
+        }
+    }
+}
+
+/// Creates the player of a new connection and puts them where their wallet
+/// was last seen, or in the spawn scene.
+async fn start_session(
+    state: &AppState,
+    wallet: Option<String>,
+    address: IpAddr,
+    protocol: u8,
+    mute: Option<Sanction>,
+) -> Option<Session> {
+    let (client_sender, outbox) = mpsc::unbounded_channel();
+    let id = Uuid::new_v4().to_string();
+    let resume_token = Uuid::new_v4().simple().to_string();
+
+    let items = match &wallet {
+        Some(wallet) => load_inventory(state, wallet).await,
+        None => Vec::new(),
+    };
+    let (x, y) = state.maps.get(SPAWN_SCENE)?.spawn;
+    let mut new_player = Player {
+        id: id.clone(),
+        x,
+        y,
+        anim: "idle-down".to_string(),
+        scene: SPAWN_SCENE.to_string(),
+        wallet: wallet.clone(),
+        cosmetics: items.iter().map(|item| item.name.clone()).collect(),
+    };
+    let last_position = match &wallet {
+        Some(wallet) => load_position(state, wallet).await,
+        None => None,
+    };
+    // Maps change; a saved spot that is now a wall falls back to the spawn
+    let returning = last_position.and_then(|position| {
+        let map = state.maps.get(&position.scene)?;
+        let (x, y) = movement::entry_point(map, position.x, position.y)?;
+        Some(PlayerPosition { x, y, ..position })
+    });
+    let is_returning = returning.is_some();
+    if let Some(position) = returning {
+        new_player.x = position.x;
+        new_player.y = position.y;
+        new_player.anim = position.anim;
+        new_player.scene = position.scene;
+    }
+    let welcome_msg = GameMessage::Welcome {
+        id: id.clone(),
+        wallet: wallet.clone(),
+        resume_token: resume_token.clone(),
+        resumed: false,
+        protocol,
     };
+    room::send(&client_sender, &welcome_msg);
+    if wallet.is_some() {
+        room::send(&client_sender, &GameMessage::Inventory { items });
+    }
 
-    {
-        state.clients.lock().unwrap().remove(&id);
-        state.players.lock().unwrap().remove(&id);
+    let Some(room) = state
+        .rooms
+        .join(new_player, client_sender.clone(), MoveBudget::full())
+    else {
+        eprintln!("No room for the spawn scene, dropping {}", id);
+        return None;
+    };
+    if is_returning {
+        // Moves the client from its own spawn point to the saved one
+        room.send(RoomCommand::Resync { id: id.clone() });
     }
-    println!("{} disconnected", id);
+    state
+        .chat
+        .connect(
+            &id,
+            wallet.clone(),
+            address,
+            protocol,
+            mute,
+            client_sender.clone(),
+        )
+        .await;
+    state
+        .chat
+        .join(&id, &chat::scene_channel(&room.id.scene))
+        .await;
 
-    let disconnect_msg = GameMessage::UserDisconnected { id: id.clone() };
-    broadcast_message(&disconnect_msg, &id, &state.clients, &state.players, scene.as_deref());
+    Some(Session {
+        id,
+        wallet,
+        protocol,
+        resume_token,
+        room,
+        sender: client_sender,
+        outbox,
+    })
 }
 
-fn broadcast_message(
-    msg: &GameMessage,
-    skip_id: &str,
-    clients: &Clients,
-    players: &Players,
-    target_scene: Option<&str>,
-) {
-    if let Ok(json) = serde_json::to_string(msg) {
-        let clients_guard = clients.lock().unwrap();
-        let players_guard = players.lock().unwrap();
-
-        for (id, sender) in clients_guard.iter() {
-            if id != skip_id {
-                let should_send = if let Some(scene) = target_scene {
-                    if let Some(player) = players_guard.get(id) {
-                        player.scene == scene
-                    } else {
-                        false
+/// Forwards queued messages to the socket until told to stop, then hands
+/// the queue back so a suspended session keeps collecting messages.
+async fn write_outbox(
+    mut sink: SplitSink<WebSocket, Message>,
+    mut outbox: Outbox,
+    mut stop: oneshot::Receiver<()>,
+) -> Outbox {
+    loop {
+        tokio::select! {
+            _ = &mut stop => break,
+            msg = outbox.recv() => match msg {
+                Some(Ok(msg)) => {
+                    if let Err(e) = sink.send(msg).await {
+                        eprintln!("error sending websocket msg: {}", e);
+                        break;
                     }
-                } else {
-                    true
-                };
-
-                if should_send {
-                    let _ = sender.send(Ok(Message::Text(json.clone())));
                 }
+                Some(Err(_)) | None => break,
+            },
+        }
+    }
+    outbox
+}
+
+/// Moves the player to the spawn point of the scene they switched to.
+/// Returns `None`, leaving them where they were, if the scene is unknown
+/// or cannot be reached from their current one.
+async fn change_room(
+    state: &AppState,
+    room: &RoomHandle,
+    id: &str,
+    sender: &ClientSender,
+    anim: String,
+    scene: String,
+) -> Option<RoomHandle> {
+    if !state.maps.is_exit(&room.id.scene, &scene) {
+        return None;
+    }
+    // Scenes are switched through menus, so the client cannot pick its spot
+    let (x, y) = state.maps.get(&scene)?.spawn;
+
+    let mut player = room.leave(id).await?;
+    state.rooms.release(room);
+    player.x = x;
+    player.y = y;
+    player.anim = anim;
+    player.scene = scene;
+
+    let next = state
+        .rooms
+        .join(player, sender.clone(), MoveBudget::full())?;
+    // Moves the client to the spawn point if its own differs
+    next.send(RoomCommand::Resync { id: id.to_string() });
+    Some(next)
+}
+
+/// Whether the wallet belongs to a region or guild by its indexed history.
+/// Guests belong to none, and a failed lookup keeps the channel closed.
+async fn is_member(state: &AppState, wallet: Option<&str>, channel: &MemberChannel) -> bool {
+    let Some(wallet) = wallet else {
+        return false;
+    };
+    let member = match channel {
+        MemberChannel::Region(region_id) => {
+            RegionRepository::new(state.db.clone())
+                .is_member(wallet, *region_id)
+                .await
+        }
+        MemberChannel::Guild(guild_name) => {
+            PoliticsRepository::new(state.db.clone())
+                .is_guild_member(wallet, guild_name)
+                .await
+        }
+    };
+    member.unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to check {} membership of {}: {:?}",
+            channel.name(),
+            wallet,
+            e
+        );
+        false
+    })
+}
+
+fn reject_chat(sender: &ClientSender, reason: String) {
+    room::send(sender, &GameMessage::ChatRejected { reason });
+}
+
+/// GameStore items the wallet owns. A failed lookup only costs the player
+/// their cosmetics, so it does not block the connection.
+async fn load_inventory(state: &AppState, wallet: &str) -> Vec<InventoryItem> {
+    match StoreRepository::new(state.db.clone())
+        .find_inventory(wallet)
+        .await
+    {
+        Ok(items) => items,
+        Err(e) => {
+            eprintln!("Failed to load inventory for {}: {:?}", wallet, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Where the wallet's avatar was last saved. Like the inventory, a failed
+/// lookup only costs the player their spot.
+async fn load_position(state: &AppState, wallet: &str) -> Option<PlayerPosition> {
+    match PositionRepository::new(state.db.clone())
+        .find_position(wallet)
+        .await
+    {
+        Ok(position) => position,
+        Err(e) => {
+            eprintln!("Failed to load position for {}: {:?}", wallet, e);
+            None
+        }
+    }
+}
+
+/// Mutes and bans in force for the wallet. A failed lookup lets the player
+/// in rather than locking everyone out while the database is down.
+async fn load_sanctions(state: &AppState, wallet: &str) -> Vec<Sanction> {
+    match ModerationRepository::new(state.db.clone())
+        .find_active(wallet)
+        .await
+    {
+        Ok(sanctions) => sanctions,
+        Err(e) => {
+            eprintln!("Failed to load sanctions for {}: {:?}", wallet, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Relays chain events from the indexer to every connected player.
+pub async fn forward_domain_events(state: AppState, mut events: broadcast::Receiver<DomainEvent>) {
+    loop {
+        match events.recv().await {
+            Ok(event) => state.rooms.broadcast(&GameMessage::from(event)),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                eprintln!("Dropped {} domain events for slow websocket relay", skipped);
             }
+            Err(broadcast::error::RecvError::Closed) => break,
         }
     }
 }
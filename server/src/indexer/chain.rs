@@ -0,0 +1,58 @@
+use crate::models::chain::LogMeta;
+use alloy::{
+    primitives::{TxHash, U256},
+    providers::{Provider, RootProvider},
+    rpc::types::{BlockNumberOrTag, Header, Log, Transaction},
+    transports::BoxTransport,
+};
+use anyhow::{Context, Result, anyhow};
+
+/// Boxed so the same provider serves `http://` and `ws://` RPC URLs.
+pub type ChainProvider = RootProvider<BoxTransport>;
+
+pub fn log_meta(log: &Log) -> Result<LogMeta> {
+    Ok(LogMeta {
+        block_number: log.block_number.context("log without block number")? as i64,
+        tx_hash: log
+            .transaction_hash
+            .context("log without transaction hash")?
+            .to_string(),
+        log_index: log.log_index.context("log without log index")? as i64,
+    })
+}
+
+/// Region ids and epochs are small on chain but typed as uint256.
+pub fn u256_to_i64(value: U256) -> Result<i64> {
+    i64::try_from(value).map_err(|_| anyhow!("value {} does not fit in i64", value))
+}
+
+/// Timestamp of the block a log was mined in. Uses `blockTimestamp` when the
+/// node includes it in the log, otherwise fetches the block header.
+pub async fn block_timestamp(provider: &ChainProvider, log: &Log) -> Result<u64> {
+    if let Some(timestamp) = log.block_timestamp {
+        return Ok(timestamp);
+    }
+
+    let number = log.block_number.context("log without block number")?;
+    let block = provider
+        .get_block_by_number(BlockNumberOrTag::Number(number), false)
+        .await?
+        .with_context(|| format!("block {} not found", number))?;
+
+    Ok(block.header.timestamp)
+}
+
+pub async fn block_header(provider: &ChainProvider, number: u64) -> Result<Option<Header>> {
+    let block = provider
+        .get_block_by_number(BlockNumberOrTag::Number(number), false)
+        .await?;
+
+    Ok(block.map(|block| block.header))
+}
+
+pub async fn transaction(provider: &ChainProvider, hash: TxHash) -> Result<Transaction> {
+    provider
+        .get_transaction_by_hash(hash)
+        .await?
+        .with_context(|| format!("transaction {} not found", hash))
+}
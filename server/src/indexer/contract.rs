@@ -10,4 +10,52 @@ sol! {
         uint256 amount,
         uint256 tax
     );
+
+    // OligarchyVoter
+    event BribeDeposited(uint256 indexed epoch, uint256 indexed regionId, uint256 amount);
+    event BribeSeized(uint256 indexed epoch, uint256 fromRegion, uint256 toRegion, uint256 amount);
+    event Voted(address indexed voter, uint256 indexed regionId, uint256 weight);
+    event BribeClaimed(address indexed voter, uint256 amount);
+
+    function claimBribe(uint256 _epoch, uint256 _regionId);
+
+    // WarTheater
+    event WarDeclared(uint256 epoch, uint256 attacker, uint256 defender);
+    event TroopsEnlisted(address user, uint256 regionId, uint256 amount, bool isAttack);
+    event WarResult(uint256 epoch, uint256 attacker, uint256 defender, bool success);
+
+    // RegionPolitics
+    event Nominated(uint256 indexed regionId, uint256 indexed epoch, address indexed candidate, string guild);
+    event ElectionVoted(uint256 indexed regionId, uint256 indexed epoch, address voter, address candidate, uint256 weight);
+    event GovernorElected(uint256 indexed regionId, uint256 indexed epoch, address governor, uint256 votes);
+    event RevolutionStarted(uint256 indexed regionId, uint256 indexed epoch, address provocateur);
+    event RevolutionSupported(uint256 indexed regionId, uint256 indexed epoch, address supporter, uint256 weight);
+    event RevolutionExecuted(uint256 indexed regionId, uint256 indexed epoch, bool success, address oustedGovernor);
+
+    // GameStore
+    event ItemAdded(uint256 itemId, string name, uint256 price);
+    event ItemPurchased(address indexed buyer, uint256 indexed itemId, uint256 price);
+}
+
+/// VeOligarchy. `Deposit` hashes the same as RegionFarmDynamic's (only the
+/// indexed flags differ) and `Withdraw` has fewer fields, so these are
+/// namespaced and dispatched by contract address.
+pub mod ve {
+    use alloy::sol;
+
+    sol! {
+        event Deposit(address indexed provider, uint256 value, uint256 locktime);
+        event Withdraw(address indexed provider, uint256 value);
+    }
+}
+
+/// LandGenesis (ERC-721). Its `Transfer` shares the ERC-20 signature hash but
+/// indexes `tokenId`, so it lives in its own namespace.
+pub mod land {
+    use alloy::sol;
+
+    sol! {
+        event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+        event LandMinted(address indexed buyer, uint256 indexed tokenId, uint256 tierId);
+    }
 }
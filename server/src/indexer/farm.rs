@@ -0,0 +1,86 @@
+use crate::indexer::chain::{self, ChainProvider};
+use crate::indexer::contract::{Deposit, Withdraw};
+use crate::repositories::farm_repo::FarmRepository;
+use crate::repositories::user_repo::UserRepository;
+use crate::utils::epoch::epoch_at;
+use alloy::{rpc::types::Log, sol_types::SolEvent};
+use anyhow::Result;
+use sqlx::PgConnection;
+
+pub async fn handle_log(
+    provider: &ChainProvider,
+    user_repo: &UserRepository,
+    farm_repo: &FarmRepository,
+    conn: &mut PgConnection,
+    log: &Log,
+) -> Result<()> {
+    match log.topic0() {
+        Some(&Deposit::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<Deposit>() {
+                let meta = chain::log_meta(log)?;
+                let epoch = epoch_at(chain::block_timestamp(provider, log).await?) as i64;
+                println!(
+                    "Found Deposit on contract {:?}: user={:?}, pid={:?}, amount={:?}",
+                    log.address(),
+                    event.inner.user,
+                    event.inner.pid,
+                    event.inner.amount
+                );
+                user_repo
+                    .create_or_update_user(
+                        &mut *conn,
+                        event.inner.user.to_string(),
+                        event.inner.amount.to_string(),
+                    )
+                    .await?;
+                farm_repo
+                    .record_deposit(
+                        &mut *conn,
+                        &meta,
+                        event.inner.user.to_string(),
+                        chain::u256_to_i64(event.inner.pid)?,
+                        epoch,
+                        event.inner.amount.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        Some(&Withdraw::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<Withdraw>() {
+                let meta = chain::log_meta(log)?;
+                let epoch = epoch_at(chain::block_timestamp(provider, log).await?) as i64;
+                // The stake shrinks by the gross amount; `amount` is net of tax.
+                let negative_amount = format!("-{}", event.inner.amount + event.inner.tax);
+                println!(
+                    "Found Withdraw on contract {:?}: user={:?}, pid={:?}, amount={:?}, tax={:?}",
+                    log.address(),
+                    event.inner.user,
+                    event.inner.pid,
+                    event.inner.amount,
+                    event.inner.tax
+                );
+                user_repo
+                    .create_or_update_user(
+                        &mut *conn,
+                        event.inner.user.to_string(),
+                        negative_amount,
+                    )
+                    .await?;
+                farm_repo
+                    .record_withdraw(
+                        &mut *conn,
+                        &meta,
+                        event.inner.user.to_string(),
+                        chain::u256_to_i64(event.inner.pid)?,
+                        epoch,
+                        event.inner.amount.to_string(),
+                        event.inner.tax.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::events::DomainEvent;
+use crate::indexer::chain;
+use crate::indexer::contract::land::{LandMinted, Transfer};
+use crate::repositories::land_repo::LandRepository;
+use alloy::{rpc::types::Log, sol_types::SolEvent};
+use anyhow::Result;
+use sqlx::PgConnection;
+
+pub async fn handle_log(
+    land_repo: &LandRepository,
+    conn: &mut PgConnection,
+    log: &Log,
+) -> Result<Option<DomainEvent>> {
+    match log.topic0() {
+        Some(&LandMinted::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<LandMinted>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found LandMinted: buyer={:?}, token={}, tier={}",
+                    event.inner.buyer, event.inner.tokenId, event.inner.tierId
+                );
+                let token_id = chain::u256_to_i64(event.inner.tokenId)?;
+                let tier = chain::u256_to_i64(event.inner.tierId)?;
+                land_repo
+                    .record_land_minted(
+                        &mut *conn,
+                        &meta,
+                        token_id,
+                        tier,
+                        event.inner.buyer.to_string(),
+                    )
+                    .await?;
+                return Ok(Some(DomainEvent::LandMinted {
+                    token_id,
+                    tier,
+                    owner: event.inner.buyer.to_string(),
+                }));
+            }
+        }
+        Some(&Transfer::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<Transfer>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found Land Transfer: token={}, from={:?}, to={:?}",
+                    event.inner.tokenId, event.inner.from, event.inner.to
+                );
+                land_repo
+                    .record_transfer(
+                        &mut *conn,
+                        &meta,
+                        chain::u256_to_i64(event.inner.tokenId)?,
+                        event.inner.from.to_string(),
+                        event.inner.to.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(None)
+}
@@ -1,173 +1,612 @@
-use crate::indexer::contract::{Deposit, Mint, Transfer, Withdraw};
+use crate::events::{DomainEvent, EventBus};
+use crate::indexer::chain::{self, ChainProvider};
+use crate::indexer::window::{self, BlockWindow};
+use crate::indexer::{farm, land, politics, store, token, ve, voter, war};
+use crate::repositories::chain_repo::ChainRepository;
+use crate::repositories::farm_repo::FarmRepository;
+use crate::repositories::land_repo::LandRepository;
+use crate::repositories::leaderboard_repo::LeaderboardRepository;
+use crate::repositories::politics_repo::PoliticsRepository;
+use crate::repositories::store_repo::StoreRepository;
+use crate::repositories::token_repo::TokenRepository;
 use crate::repositories::user_repo::UserRepository;
-use alloy::sol_types::SolEvent;
+use crate::repositories::ve_repo::VeRepository;
+use crate::repositories::voter_repo::VoterRepository;
+use crate::repositories::war_repo::WarRepository;
 
 use alloy::{
-    providers::{Provider, ProviderBuilder, RootProvider},
-    rpc::types::Filter,
-    transports::http::{Client, Http},
+    primitives::{Address, B256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::{Filter, Log},
 };
-use anyhow::Result;
-use sqlx::{PgPool, Row};
-use std::{str::FromStr, time::Duration};
-use tokio::time::sleep;
-use url::Url;
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+    time::Duration,
+};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{Instant, sleep};
+
+/// How long to poll after a subscription dropped before subscribing again.
+const RESUBSCRIBE_AFTER: Duration = Duration::from_secs(30);
+/// Ranges starting this many blocks (or the confirmation depth, if larger)
+/// behind the safe block count as live. Events of older ranges are history
+/// being backfilled; they are stored but not broadcast.
+const LIVE_BLOCKS: u64 = 64;
+/// Longest the leaderboards lag behind indexed data while backfilling. Once
+/// caught up they are refreshed after every pass that changed something.
+const LEADERBOARD_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Which deployed contract an address belongs to. Several contracts emit
+/// events with the same signature hash (e.g. `Deposit`, `Transfer`), so logs
+/// are dispatched by the emitting address rather than by topic alone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContractKind {
+    MockMantle,
+    OligToken,
+    GameStore,
+    VeOligarchy,
+    OligarchyVoter,
+    RegionFarm,
+    WarTheater,
+    RegionPolitics,
+    LandGenesis,
+}
+
+impl ContractKind {
+    /// Name stored with the kind's cursor and applied logs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContractKind::MockMantle => "MockMantle",
+            ContractKind::OligToken => "OligToken",
+            ContractKind::GameStore => "GameStore",
+            ContractKind::VeOligarchy => "VeOligarchy",
+            ContractKind::OligarchyVoter => "OligarchyVoter",
+            ContractKind::RegionFarm => "RegionFarm",
+            ContractKind::WarTheater => "WarTheater",
+            ContractKind::RegionPolitics => "RegionPolitics",
+            ContractKind::LandGenesis => "LandGenesis",
+        }
+    }
+}
+
+/// A contract to index and the first block its history starts at.
+#[derive(Clone, Debug)]
+pub struct IndexedContract {
+    pub kind: ContractKind,
+    pub address: String,
+    pub start_block: u64,
+}
+
+struct Repositories {
+    chain: ChainRepository,
+    users: UserRepository,
+    farm: FarmRepository,
+    voter: VoterRepository,
+    war: WarRepository,
+    politics: PoliticsRepository,
+    land: LandRepository,
+    ve: VeRepository,
+    store: StoreRepository,
+    tokens: TokenRepository,
+    leaderboards: LeaderboardRepository,
+}
+
+/// Everything an indexing pass needs, shared by the polling and the
+/// subscription loops.
+struct Indexer {
+    provider: ChainProvider,
+    db: PgPool,
+    repos: Repositories,
+    contracts: HashMap<Address, Vec<ContractKind>>,
+    confirmations: u64,
+    window: BlockWindow,
+    events: EventBus,
+    /// Set when indexed data changed since the leaderboards were refreshed.
+    leaderboards_stale: bool,
+    leaderboards_refreshed: Instant,
+}
+
+/// Outcome of one indexing pass.
+struct Pass {
+    /// Every cursor reached the safe block.
+    caught_up: bool,
+    /// Logs were applied or rolled back.
+    changed: bool,
+}
+
+impl Indexer {
+    /// Runs one pass and reports whether every cursor reached the safe block.
+    /// A failed pass counts as caught up so callers back off before retrying.
+    async fn step(&mut self) -> bool {
+        let caught_up = match process_blocks(
+            &self.provider,
+            &self.db,
+            &self.repos,
+            &self.contracts,
+            self.confirmations,
+            &mut self.window,
+            &self.events,
+        )
+        .await
+        {
+            Ok(pass) => {
+                self.leaderboards_stale |= pass.changed;
+                pass.caught_up
+            }
+            Err(e) => {
+                eprintln!("Indexer Error: {:?}", e);
+                sleep(Duration::from_secs(3)).await; // Retry delay
+                true
+            }
+        };
+        self.refresh_leaderboards(caught_up).await;
+        caught_up
+    }
+
+    /// Recomputes the leaderboards over all history, so during a backfill it
+    /// only runs every `LEADERBOARD_REFRESH_INTERVAL`.
+    async fn refresh_leaderboards(&mut self, caught_up: bool) {
+        let due =
+            caught_up || self.leaderboards_refreshed.elapsed() >= LEADERBOARD_REFRESH_INTERVAL;
+        if !self.leaderboards_stale || !due {
+            return;
+        }
+        self.leaderboards_refreshed = Instant::now();
+        match self.repos.leaderboards.refresh().await {
+            Ok(()) => self.leaderboards_stale = false,
+            Err(e) => eprintln!("Failed to refresh leaderboards: {:?}", e),
+        }
+    }
+
+    async fn catch_up(&mut self) {
+        while !self.step().await {}
+    }
+}
 
-pub async fn run_indexer(db: PgPool, rpc_url: String, contract_addresses: Vec<String>) {
+pub async fn run_indexer(
+    db: PgPool,
+    rpc_url: String,
+    contracts: Vec<IndexedContract>,
+    confirmations: u64,
+    max_block_range: u64,
+    events: EventBus,
+) {
     println!("Starting Indexer Service...");
     println!("RPC URL: {}", rpc_url);
-    println!("Contract Addresses: {:?}", contract_addresses);
+    println!("Contracts: {:?}", contracts);
+    println!("Confirmations: {}", confirmations);
+    println!("Max block range: {}", max_block_range);
 
-    let url = Url::parse(&rpc_url).expect("Invalid RPC URL");
-    let provider = ProviderBuilder::new().on_http(url);
+    let pubsub = rpc_url.starts_with("ws://") || rpc_url.starts_with("wss://");
+    let provider = ProviderBuilder::new()
+        .on_builtin(&rpc_url)
+        .await
+        .expect("Failed to connect to RPC");
 
-    let contract_addrs: Vec<alloy::primitives::Address> = contract_addresses
-        .iter()
-        .map(|addr| {
-            alloy::primitives::Address::from_str(addr)
-                .expect(&format!("Invalid Contract Address: {}", addr))
-        })
-        .collect();
-    let user_repo = UserRepository::new(db.clone());
+    // The default deployment shares an address between contracts, so one
+    // address can map to several kinds.
+    let mut contract_kinds: HashMap<Address, Vec<ContractKind>> = HashMap::new();
+    for contract in &contracts {
+        let addr = Address::from_str(&contract.address)
+            .unwrap_or_else(|_| panic!("Invalid Contract Address: {}", contract.address));
+        contract_kinds.entry(addr).or_default().push(contract.kind);
+    }
+    let repos = Repositories {
+        chain: ChainRepository::new(db.clone()),
+        users: UserRepository::new(db.clone()),
+        farm: FarmRepository::new(db.clone()),
+        voter: VoterRepository::new(db.clone()),
+        war: WarRepository::new(db.clone()),
+        politics: PoliticsRepository::new(db.clone()),
+        land: LandRepository::new(db.clone()),
+        ve: VeRepository::new(db.clone()),
+        store: StoreRepository::new(db.clone()),
+        tokens: TokenRepository::new(db.clone()),
+        leaderboards: LeaderboardRepository::new(db.clone()),
+    };
+
+    for contract in &contracts {
+        let addr = Address::from_str(&contract.address).expect("checked above");
+        let created = repos
+            .chain
+            .ensure_cursor(
+                &addr.to_string(),
+                contract.kind.as_str(),
+                contract.start_block as i64,
+            )
+            .await
+            .expect("Failed to create indexer cursor");
+        if created {
+            println!(
+                "New contract {:?} at {}: indexing from block {}",
+                contract.kind, addr, contract.start_block
+            );
+        }
+    }
+
+    // A refresh may have been lost to a restart after its range committed
+    if let Err(e) = repos.leaderboards.refresh().await {
+        eprintln!("Failed to refresh leaderboards: {:?}", e);
+    }
+
+    let mut indexer = Indexer {
+        provider,
+        db,
+        repos,
+        contracts: contract_kinds,
+        confirmations,
+        window: BlockWindow::new(max_block_range),
+        events,
+        leaderboards_stale: false,
+        leaderboards_refreshed: Instant::now(),
+    };
+
+    loop {
+        if pubsub {
+            match follow_subscriptions(&mut indexer).await {
+                Ok(()) => println!("Subscription dropped, falling back to polling"),
+                Err(e) => eprintln!("Subscription failed, falling back to polling: {:?}", e),
+            }
+        }
+        poll(&mut indexer, pubsub.then_some(RESUBSCRIBE_AFTER)).await;
+    }
+}
 
+/// Indexes whenever the node pushes a new head or a log of an indexed
+/// contract. Returns once either subscription is closed.
+async fn follow_subscriptions(indexer: &mut Indexer) -> Result<()> {
+    let addresses: Vec<Address> = indexer.contracts.keys().copied().collect();
+    let mut heads = indexer.provider.subscribe_blocks().await?;
+    let mut logs = indexer
+        .provider
+        .subscribe_logs(&Filter::new().address(addresses))
+        .await?;
+    println!("Subscribed to new heads and logs");
+
+    indexer.catch_up().await;
     loop {
-        if let Err(e) = process_blocks(&provider, &db, &user_repo, &contract_addrs).await {
-            eprintln!("Indexer Error: {:?}", e);
-            sleep(Duration::from_secs(3)).await; // Retry delay
+        let notified = tokio::select! {
+            head = heads.recv_any() => head.map(drop),
+            log = logs.recv_any() => log.map(drop),
+        };
+        // Lagging only means the next pass covers more blocks
+        if let Err(RecvError::Closed) = notified {
+            return Ok(());
         }
-        sleep(Duration::from_secs(2)).await; // Polling interval
+        indexer.catch_up().await;
     }
 }
 
+/// Polls the chain, forever or until `limit` has passed.
+async fn poll(indexer: &mut Indexer, limit: Option<Duration>) {
+    let started = Instant::now();
+    while limit.is_none_or(|limit| started.elapsed() < limit) {
+        // Contracts that are still backfilling continue right away
+        if indexer.step().await {
+            sleep(Duration::from_secs(2)).await; // Polling interval
+        }
+    }
+}
+
+/// Advances every contract cursor by at most one chunk. Contracts whose
+/// cursors are at the same block are fetched together, starting with the ones
+/// closest to the head, so a contract that is backfilling never holds up the
+/// others.
 async fn process_blocks(
-    provider: &RootProvider<Http<Client>>,
+    provider: &ChainProvider,
     db: &PgPool,
-    user_repo: &UserRepository,
-    contract_addrs: &[alloy::primitives::Address],
-) -> Result<()> {
-    // 1. Get current block number from chain
+    repos: &Repositories,
+    contracts: &HashMap<Address, Vec<ContractKind>>,
+    confirmations: u64,
+    window: &mut BlockWindow,
+    events: &EventBus,
+) -> Result<Pass> {
+    // 1. Get current block number from chain, minus the confirmation depth
     let current_block = provider.get_block_number().await?;
+    let safe_block = current_block.saturating_sub(confirmations);
 
-    // 2. Get last processed block from DB
-    let last_processed_block: i64 =
-        sqlx::query("SELECT last_processed_block FROM indexer_state WHERE id = 1")
-            .fetch_one(db)
-            .await?
-            .get("last_processed_block");
-
-    let last_processed_block = last_processed_block as u64;
+    // 2. Get the cursors of the configured contracts from DB
+    let addresses: Vec<String> = contracts.keys().map(Address::to_string).collect();
+    let mut groups: BTreeMap<u64, Vec<(Address, ContractKind)>> = BTreeMap::new();
+    for cursor in repos.chain.find_cursors(&addresses).await? {
+        let addr = Address::from_str(&cursor.contract_address)?;
+        // Cursors of kinds no longer configured at the address stay put
+        let Some(&kind) = contracts
+            .get(&addr)
+            .into_iter()
+            .flatten()
+            .find(|kind| kind.as_str() == cursor.contract_kind)
+        else {
+            continue;
+        };
+        groups
+            .entry(cursor.last_processed_block as u64)
+            .or_default()
+            .push((addr, kind));
+    }
+    let last_processed_block = groups.keys().next_back().copied().unwrap_or_default();
 
-    // Handle Chain Reset (Dev Environment)
-    if current_block < last_processed_block {
+    // 3. Detect reorgs (and dev chain resets) before indexing on top of them
+    if !builds_on_indexed_tip(provider, repos, last_processed_block, current_block).await? {
+        let fork_block = find_fork_block(provider, repos, last_processed_block).await?;
         println!(
-            "⚠️  Chain Reset Detected! (Current: {}, Last: {})",
-            current_block, last_processed_block
+            "⚠️  Reorg Detected! (Last: {}, Current: {}) Rolling back to block {}",
+            last_processed_block, current_block, fork_block
         );
-        println!("Resetting indexer status...");
-        sqlx::query(
-            "UPDATE indexer_state SET last_processed_block = 0, updated_at = NOW() WHERE id = 1",
+        rollback(db, repos, fork_block).await?;
+        return Ok(Pass {
+            caught_up: false,
+            changed: true,
+        });
+    }
+
+    // 4. Advance each group by one chunk
+    let mut pass = Pass {
+        caught_up: true,
+        changed: false,
+    };
+    for (&cursor, targets) in groups.iter().rev() {
+        if cursor >= safe_block {
+            continue;
+        }
+        let live = safe_block - cursor <= confirmations.max(LIVE_BLOCKS);
+        let events = live.then_some(events);
+        let (to_block, changed) = process_chunk(
+            provider, db, repos, targets, cursor, safe_block, window, events,
         )
-        .execute(db)
         .await?;
-        return Ok(());
+        pass.caught_up &= to_block >= safe_block;
+        pass.changed |= changed;
     }
 
-    if current_block <= last_processed_block {
-        return Ok(());
+    Ok(pass)
+}
+
+/// Fetches the logs after `cursor` in one request, sized to what the
+/// provider accepts, applies them and publishes the resulting domain events
+/// to `events`, unless the range is backfill.
+/// Returns the last block applied and whether any log was new.
+#[allow(clippy::too_many_arguments)]
+async fn process_chunk(
+    provider: &ChainProvider,
+    db: &PgPool,
+    repos: &Repositories,
+    targets: &[(Address, ContractKind)],
+    cursor: u64,
+    safe_block: u64,
+    window: &mut BlockWindow,
+    events: Option<&EventBus>,
+) -> Result<(u64, bool)> {
+    let from_block = cursor + 1;
+    let mut addresses: Vec<Address> = targets.iter().map(|(addr, _)| *addr).collect();
+    addresses.sort();
+    addresses.dedup();
+    loop {
+        let to_block = safe_block.min(from_block + window.size() - 1);
+        let filter = Filter::new()
+            .address(addresses.clone())
+            .from_block(from_block)
+            .to_block(to_block);
+
+        let logs = match provider.get_logs(&filter).await {
+            Ok(logs) => logs,
+            Err(e) if window::is_range_too_large(&e) && window.shrink() => {
+                println!(
+                    "Blocks {} to {} rejected by provider, retrying {} blocks at a time",
+                    from_block,
+                    to_block,
+                    window.size()
+                );
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        println!(
+            "Indexing blocks {} to {} for {} contract(s) ({} logs)",
+            from_block,
+            to_block,
+            targets.len(),
+            logs.len()
+        );
+        let (applied, applied_events) =
+            apply_range(provider, db, repos, targets, logs, to_block).await?;
+        if let Some(events) = events {
+            for event in applied_events {
+                // Nobody listening is fine; the events are also in the database
+                let _ = events.send(event);
+            }
+        }
+
+        window.grow();
+        return Ok((to_block, applied > 0));
     }
+}
 
-    println!(
-        "Indexing blocks {} to {}",
-        last_processed_block + 1,
-        current_block
-    );
+/// Applies the logs of one chunk, its block hashes and the cursors in a single
+/// transaction, so a crash midway leaves no partially applied range behind.
+/// Returns the number of newly applied logs and their domain events once
+/// committed.
+async fn apply_range(
+    provider: &ChainProvider,
+    db: &PgPool,
+    repos: &Repositories,
+    targets: &[(Address, ContractKind)],
+    mut logs: Vec<Log>,
+    to_block: u64,
+) -> Result<(usize, Vec<DomainEvent>)> {
+    let mut events = Vec::new();
+    let mut applied = 0;
+    let mut block_hashes: BTreeMap<u64, B256> = BTreeMap::new();
+    let mut tx = db.begin().await?;
 
-    // 3. Query Logs for each contract
-    for &contract_addr in contract_addrs {
-        // Remove specific event filters to ensure we catch everything matching the address
-        let filter = Filter::new()
-            .address(contract_addr)
-            .from_block(last_processed_block + 1)
-            .to_block(current_block);
-
-        let logs = provider.get_logs(&filter).await?;
-
-        for log in logs {
-            let topic = log.topics().first();
-
-            match topic {
-                // Some(&Transfer::SIGNATURE_HASH) => {
-                //     if let Ok(event) = log.log_decode::<Transfer>() {
-                //         // If from is 0x0...0, it's a mint
-                //         if event.inner.from == alloy::primitives::Address::ZERO {
-                //             println!(
-                //                 "Found Mint (Transfer) on contract {:?}: to={:?}, amount={:?}",
-                //                 contract_addr, event.inner.to, event.inner.value
-                //             );
-                //         }
-                //     }
-                // }
-                Some(&Deposit::SIGNATURE_HASH) => {
-                    if let Ok(event) = log.log_decode::<Deposit>() {
-                        println!(
-                            "Found Deposit on contract {:?}: user={:?}, pid={:?}, amount={:?}",
-                            contract_addr, event.inner.user, event.inner.pid, event.inner.amount
-                        );
-                        user_repo
-                            .create_or_update_user(
-                                event.inner.user.to_string(),
-                                event.inner.amount.to_string(),
-                            )
-                            .await?;
-                    }
+    // Logs of all contracts come back in one response; keep chain order
+    logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+    // 5. Dispatch each log to the handlers of the emitting contract
+    for log in logs {
+        if let (Some(number), Some(hash)) = (log.block_number, log.block_hash) {
+            block_hashes.insert(number, hash);
+        }
+
+        let meta = chain::log_meta(&log)?;
+        let kinds = targets
+            .iter()
+            .filter(|(addr, _)| *addr == log.address())
+            .map(|(_, kind)| kind);
+        for kind in kinds {
+            // Replaying a range is a no-op for logs the kind already applied
+            if !repos
+                .chain
+                .mark_applied(&mut tx, &meta, kind.as_str())
+                .await?
+            {
+                continue;
+            }
+            applied += 1;
+
+            let event = match kind {
+                ContractKind::RegionFarm => {
+                    farm::handle_log(provider, &repos.users, &repos.farm, &mut tx, &log).await?;
+                    None
                 }
-                Some(&Withdraw::SIGNATURE_HASH) => {
-                    if let Ok(event) = log.log_decode::<Withdraw>() {
-                        let negative_amount = format!("-{}", event.inner.amount);
-                        println!(
-                            "Found Withdraw on contract {:?}: user={:?}, pid={:?}, amount={:?}, tax={:?}",
-                            contract_addr,
-                            event.inner.user,
-                            event.inner.pid,
-                            event.inner.amount,
-                            event.inner.tax
-                        );
-                        user_repo
-                            .create_or_update_user(
-                                event.inner.user.to_string(),
-                                negative_amount.to_string(),
-                            )
-                            .await?;
-                    }
+                ContractKind::OligarchyVoter => {
+                    voter::handle_log(provider, &repos.voter, &mut tx, &log).await?
                 }
-                // Some(&Mint::SIGNATURE_HASH) => {
-                //     if let Ok(event) = log.log_decode::<Mint>() {
-                //         println!(
-                //             "Found Legacy Mint on contract {:?}: wallet={:?}, amount={:?}",
-                //             contract_addr, event.inner.wallet, event.inner.initialBalance
-                //         );
-                //         user_repo
-                //             .create_or_update_user(
-                //                 event.inner.wallet.to_string(),
-                //                 event.inner.initialBalance.to_string(),
-                //             )
-                //             .await?;
-                //     }
-                // }
-                _ => {
-                    // println!("Unknown event on contract {:?}", contract_addr);
+                ContractKind::WarTheater => {
+                    war::handle_log(provider, &repos.war, &mut tx, &log).await?
                 }
-            }
+                ContractKind::RegionPolitics => {
+                    politics::handle_log(provider, &repos.politics, &repos.ve, &mut tx, &log)
+                        .await?
+                }
+                ContractKind::LandGenesis => land::handle_log(&repos.land, &mut tx, &log).await?,
+                ContractKind::VeOligarchy => {
+                    ve::handle_log(&repos.ve, &mut tx, &log).await?;
+                    None
+                }
+                ContractKind::GameStore => {
+                    store::handle_log(&repos.store, &mut tx, &log).await?;
+                    None
+                }
+                ContractKind::MockMantle | ContractKind::OligToken => {
+                    token::handle_log(&repos.tokens, &mut tx, &log).await?;
+                    None
+                }
+            };
+            events.extend(event);
         }
     }
 
-    // 5. Update Indexer State
-    sqlx::query(
-        "UPDATE indexer_state SET last_processed_block = $1, updated_at = NOW() WHERE id = 1",
-    )
-    .bind(current_block as i64)
-    .execute(db)
-    .await?;
+    // The tip is always recorded so the next range can check its parent hash.
+    let tip = chain::block_header(provider, to_block)
+        .await?
+        .and_then(|header| header.hash)
+        .with_context(|| format!("block {} not found", to_block))?;
+    block_hashes.insert(to_block, tip);
+
+    // 6. Update Indexer State
+    for (number, hash) in block_hashes {
+        repos
+            .chain
+            .record_block(&mut tx, number as i64, hash.to_string())
+            .await?;
+    }
+    let (addresses, kinds): (Vec<String>, Vec<String>) = targets
+        .iter()
+        .map(|(addr, kind)| (addr.to_string(), kind.as_str().to_string()))
+        .unzip();
+    repos
+        .chain
+        .set_last_processed_block(&mut tx, &addresses, &kinds, to_block as i64)
+        .await?;
+    tx.commit().await?;
+
+    Ok((applied, events))
+}
+
+/// Whether the chain still contains the last indexed block: the parent hash
+/// of the next block (or the block itself, if there is no next block yet)
+/// must match the hash stored when it was indexed.
+async fn builds_on_indexed_tip(
+    provider: &ChainProvider,
+    repos: &Repositories,
+    last_processed_block: u64,
+    current_block: u64,
+) -> Result<bool> {
+    let Some(stored_hash) = repos.chain.block_hash(last_processed_block as i64).await? else {
+        return Ok(true);
+    };
+
+    let canonical_hash = if current_block > last_processed_block {
+        chain::block_header(provider, last_processed_block + 1)
+            .await?
+            .map(|header| header.parent_hash)
+    } else {
+        chain::block_header(provider, last_processed_block)
+            .await?
+            .and_then(|header| header.hash)
+    };
+
+    Ok(canonical_hash.map(|hash| hash.to_string()) == Some(stored_hash))
+}
+
+/// Highest recorded block whose hash is still canonical, or 0 if none is.
+/// Hashes chain together, so once a block matches every earlier one does too
+/// and the recorded blocks can be binary searched.
+async fn find_fork_block(
+    provider: &ChainProvider,
+    repos: &Repositories,
+    last_processed_block: u64,
+) -> Result<u64> {
+    let blocks = repos
+        .chain
+        .blocks_up_to(last_processed_block as i64)
+        .await?;
+
+    let (mut low, mut high) = (0, blocks.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        let (number, ref stored_hash) = blocks[mid];
+        let canonical_hash = chain::block_header(provider, number as u64)
+            .await?
+            .and_then(|header| header.hash);
+
+        if canonical_hash.map(|hash| hash.to_string()).as_ref() == Some(stored_hash) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok(if low == 0 {
+        0
+    } else {
+        blocks[low - 1].0 as u64
+    })
+}
+
+/// Removes every row produced after `fork_block`, rebuilds the aggregates
+/// and rewinds the cursors, all in one transaction. The leaderboards are
+/// left to the next refresh.
+async fn rollback(db: &PgPool, repos: &Repositories, fork_block: u64) -> Result<()> {
+    let block = fork_block as i64;
+    let mut tx = db.begin().await?;
+
+    repos.farm.rollback_after(&mut tx, block).await?;
+    repos.users.rebuild_balances(&mut tx).await?;
+    repos.voter.rollback_after(&mut tx, block).await?;
+    repos.war.rollback_after(&mut tx, block).await?;
+    repos.politics.rollback_after(&mut tx, block).await?;
+    repos.land.rollback_after(&mut tx, block).await?;
+    repos.ve.rollback_after(&mut tx, block).await?;
+    repos.store.rollback_after(&mut tx, block).await?;
+    repos.tokens.rollback_after(&mut tx, block).await?;
+    repos.chain.rollback_after(&mut tx, block).await?;
 
+    tx.commit().await?;
     Ok(())
 }
@@ -1,2 +1,12 @@
+pub mod chain;
 pub mod contract;
+pub mod farm;
+pub mod land;
 pub mod listener;
+pub mod politics;
+pub mod store;
+pub mod token;
+pub mod ve;
+pub mod voter;
+pub mod war;
+pub mod window;
@@ -0,0 +1,177 @@
+use crate::events::DomainEvent;
+use crate::indexer::chain::{self, ChainProvider};
+use crate::indexer::contract::{
+    ElectionVoted, GovernorElected, Nominated, RevolutionExecuted, RevolutionStarted,
+    RevolutionSupported,
+};
+use crate::repositories::politics_repo::PoliticsRepository;
+use crate::repositories::ve_repo::VeRepository;
+use alloy::{primitives::U256, rpc::types::Log, sol_types::SolEvent};
+use anyhow::Result;
+use sqlx::PgConnection;
+
+pub async fn handle_log(
+    provider: &ChainProvider,
+    politics_repo: &PoliticsRepository,
+    ve_repo: &VeRepository,
+    conn: &mut PgConnection,
+    log: &Log,
+) -> Result<Option<DomainEvent>> {
+    match log.topic0() {
+        Some(&Nominated::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<Nominated>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found Nominated: region={}, epoch={}, candidate={:?}, guild={}",
+                    event.inner.regionId,
+                    event.inner.epoch,
+                    event.inner.candidate,
+                    event.inner.guild
+                );
+                politics_repo
+                    .record_nomination(
+                        &mut *conn,
+                        &meta,
+                        chain::u256_to_i64(event.inner.regionId)?,
+                        chain::u256_to_i64(event.inner.epoch)?,
+                        event.inner.candidate.to_string(),
+                        event.inner.guild.clone(),
+                    )
+                    .await?;
+            }
+        }
+        Some(&ElectionVoted::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<ElectionVoted>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found ElectionVoted: region={}, epoch={}, voter={:?}, candidate={:?}, weight={}",
+                    event.inner.regionId,
+                    event.inner.epoch,
+                    event.inner.voter,
+                    event.inner.candidate,
+                    event.inner.weight
+                );
+                politics_repo
+                    .record_election_vote(
+                        &mut *conn,
+                        &meta,
+                        chain::u256_to_i64(event.inner.regionId)?,
+                        chain::u256_to_i64(event.inner.epoch)?,
+                        event.inner.voter.to_string(),
+                        event.inner.candidate.to_string(),
+                        event.inner.weight.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        Some(&GovernorElected::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<GovernorElected>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found GovernorElected: region={}, epoch={}, governor={:?}, votes={}",
+                    event.inner.regionId,
+                    event.inner.epoch,
+                    event.inner.governor,
+                    event.inner.votes
+                );
+                let region_id = chain::u256_to_i64(event.inner.regionId)?;
+                let epoch = chain::u256_to_i64(event.inner.epoch)?;
+                politics_repo
+                    .record_governor_elected(
+                        &mut *conn,
+                        &meta,
+                        region_id,
+                        epoch,
+                        event.inner.governor.to_string(),
+                        event.inner.votes.to_string(),
+                    )
+                    .await?;
+                return Ok(Some(DomainEvent::GovernorElected {
+                    region_id,
+                    epoch,
+                    governor: event.inner.governor.to_string(),
+                    votes: event.inner.votes.to_string(),
+                }));
+            }
+        }
+        Some(&RevolutionStarted::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<RevolutionStarted>() {
+                let meta = chain::log_meta(log)?;
+                let started_at = chain::block_timestamp(provider, log).await?;
+                println!(
+                    "Found RevolutionStarted: region={}, epoch={}, provocateur={:?}",
+                    event.inner.regionId, event.inner.epoch, event.inner.provocateur
+                );
+                // startRevolution counts the caller's own veOLIG without emitting it.
+                let provocateur = event.inner.provocateur.to_string();
+                let provocateur_power = match ve_repo
+                    .find_lock_at(&mut *conn, &provocateur, &meta)
+                    .await?
+                {
+                    Some(lock) => lock.voting_power_at(started_at)?,
+                    None => U256::ZERO,
+                };
+                politics_repo
+                    .record_revolution_started(
+                        &mut *conn,
+                        &meta,
+                        chain::u256_to_i64(event.inner.regionId)?,
+                        chain::u256_to_i64(event.inner.epoch)?,
+                        provocateur,
+                        started_at as i64,
+                        provocateur_power.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        Some(&RevolutionSupported::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<RevolutionSupported>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found RevolutionSupported: region={}, epoch={}, supporter={:?}, weight={}",
+                    event.inner.regionId,
+                    event.inner.epoch,
+                    event.inner.supporter,
+                    event.inner.weight
+                );
+                politics_repo
+                    .record_revolution_support(
+                        &mut *conn,
+                        &meta,
+                        chain::u256_to_i64(event.inner.regionId)?,
+                        chain::u256_to_i64(event.inner.epoch)?,
+                        event.inner.supporter.to_string(),
+                        event.inner.weight.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        Some(&RevolutionExecuted::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<RevolutionExecuted>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found RevolutionExecuted: region={}, epoch={}, success={}, ousted={:?}",
+                    event.inner.regionId,
+                    event.inner.epoch,
+                    event.inner.success,
+                    event.inner.oustedGovernor
+                );
+                // The contract only emits on success, but don't trust that blindly.
+                if event.inner.success {
+                    politics_repo
+                        .record_revolution_executed(
+                            &mut *conn,
+                            &meta,
+                            chain::u256_to_i64(event.inner.regionId)?,
+                            chain::u256_to_i64(event.inner.epoch)?,
+                            event.inner.oustedGovernor.to_string(),
+                        )
+                        .await?;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,54 @@
+use crate::indexer::chain;
+use crate::indexer::contract::{ItemAdded, ItemPurchased};
+use crate::repositories::store_repo::StoreRepository;
+use alloy::{rpc::types::Log, sol_types::SolEvent};
+use anyhow::Result;
+use sqlx::PgConnection;
+
+pub async fn handle_log(
+    store_repo: &StoreRepository,
+    conn: &mut PgConnection,
+    log: &Log,
+) -> Result<()> {
+    match log.topic0() {
+        Some(&ItemAdded::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<ItemAdded>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found ItemAdded: item={}, name={}, price={}",
+                    event.inner.itemId, event.inner.name, event.inner.price
+                );
+                store_repo
+                    .record_item_added(
+                        &mut *conn,
+                        &meta,
+                        chain::u256_to_i64(event.inner.itemId)?,
+                        event.inner.name.clone(),
+                        event.inner.price.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        Some(&ItemPurchased::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<ItemPurchased>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found ItemPurchased: buyer={:?}, item={}, price={}",
+                    event.inner.buyer, event.inner.itemId, event.inner.price
+                );
+                store_repo
+                    .record_item_purchased(
+                        &mut *conn,
+                        &meta,
+                        event.inner.buyer.to_string(),
+                        chain::u256_to_i64(event.inner.itemId)?,
+                        event.inner.price.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
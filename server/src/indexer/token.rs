@@ -0,0 +1,37 @@
+use crate::indexer::chain;
+use crate::indexer::contract::Transfer;
+use crate::repositories::token_repo::TokenRepository;
+use alloy::{rpc::types::Log, sol_types::SolEvent};
+use anyhow::Result;
+use sqlx::PgConnection;
+
+pub async fn handle_log(
+    token_repo: &TokenRepository,
+    conn: &mut PgConnection,
+    log: &Log,
+) -> Result<()> {
+    if let Some(&Transfer::SIGNATURE_HASH) = log.topic0()
+        && let Ok(event) = log.log_decode::<Transfer>()
+    {
+        let meta = chain::log_meta(log)?;
+        println!(
+            "Found Transfer on token {:?}: from={:?}, to={:?}, value={}",
+            log.address(),
+            event.inner.from,
+            event.inner.to,
+            event.inner.value
+        );
+        token_repo
+            .record_transfer(
+                &mut *conn,
+                &meta,
+                log.address().to_string(),
+                event.inner.from.to_string(),
+                event.inner.to.to_string(),
+                event.inner.value.to_string(),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::indexer::chain;
+use crate::indexer::contract::ve::{Deposit, Withdraw};
+use crate::repositories::ve_repo::VeRepository;
+use alloy::{rpc::types::Log, sol_types::SolEvent};
+use anyhow::Result;
+use sqlx::PgConnection;
+
+pub async fn handle_log(ve_repo: &VeRepository, conn: &mut PgConnection, log: &Log) -> Result<()> {
+    match log.topic0() {
+        Some(&Deposit::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<Deposit>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found Ve Deposit: provider={:?}, value={}, locktime={}",
+                    event.inner.provider, event.inner.value, event.inner.locktime
+                );
+                ve_repo
+                    .record_lock(
+                        &mut *conn,
+                        &meta,
+                        event.inner.provider.to_string(),
+                        event.inner.value.to_string(),
+                        chain::u256_to_i64(event.inner.locktime)?,
+                    )
+                    .await?;
+            }
+        }
+        Some(&Withdraw::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<Withdraw>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found Ve Withdraw: provider={:?}, value={}",
+                    event.inner.provider, event.inner.value
+                );
+                ve_repo
+                    .record_withdraw(
+                        &mut *conn,
+                        &meta,
+                        event.inner.provider.to_string(),
+                        event.inner.value.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::events::DomainEvent;
+use crate::indexer::chain::{self, ChainProvider};
+use crate::indexer::contract::{BribeClaimed, BribeDeposited, BribeSeized, Voted, claimBribeCall};
+use crate::repositories::voter_repo::VoterRepository;
+use crate::utils::epoch::epoch_at;
+use alloy::{
+    rpc::types::Log,
+    sol_types::{SolCall, SolEvent},
+};
+use anyhow::{Context, Result};
+use sqlx::PgConnection;
+
+pub async fn handle_log(
+    provider: &ChainProvider,
+    voter_repo: &VoterRepository,
+    conn: &mut PgConnection,
+    log: &Log,
+) -> Result<Option<DomainEvent>> {
+    match log.topic0() {
+        Some(&BribeDeposited::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<BribeDeposited>() {
+                let meta = chain::log_meta(log)?;
+                let tx_hash = log
+                    .transaction_hash
+                    .context("log without transaction hash")?;
+                let depositor = chain::transaction(provider, tx_hash).await?.from;
+                println!(
+                    "Found BribeDeposited: epoch={}, region={}, depositor={:?}, amount={}",
+                    event.inner.epoch, event.inner.regionId, depositor, event.inner.amount
+                );
+                voter_repo
+                    .record_bribe_deposit(
+                        &mut *conn,
+                        &meta,
+                        chain::u256_to_i64(event.inner.epoch)?,
+                        chain::u256_to_i64(event.inner.regionId)?,
+                        depositor.to_string(),
+                        event.inner.amount.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        Some(&BribeSeized::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<BribeSeized>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found BribeSeized: epoch={}, from={}, to={}, amount={}",
+                    event.inner.epoch,
+                    event.inner.fromRegion,
+                    event.inner.toRegion,
+                    event.inner.amount
+                );
+                let epoch = chain::u256_to_i64(event.inner.epoch)?;
+                let from_region = chain::u256_to_i64(event.inner.fromRegion)?;
+                let to_region = chain::u256_to_i64(event.inner.toRegion)?;
+                voter_repo
+                    .record_bribe_seizure(
+                        &mut *conn,
+                        &meta,
+                        epoch,
+                        from_region,
+                        to_region,
+                        event.inner.amount.to_string(),
+                    )
+                    .await?;
+                return Ok(Some(DomainEvent::BribeSeized {
+                    epoch,
+                    from_region,
+                    to_region,
+                    amount: event.inner.amount.to_string(),
+                }));
+            }
+        }
+        Some(&Voted::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<Voted>() {
+                let meta = chain::log_meta(log)?;
+                // Voted has no epoch field; derive it from the block like the contract does.
+                let epoch = epoch_at(chain::block_timestamp(provider, log).await?);
+                println!(
+                    "Found Voted: epoch={}, region={}, voter={:?}, weight={}",
+                    epoch, event.inner.regionId, event.inner.voter, event.inner.weight
+                );
+                voter_repo
+                    .record_vote(
+                        &mut *conn,
+                        &meta,
+                        epoch as i64,
+                        chain::u256_to_i64(event.inner.regionId)?,
+                        event.inner.voter.to_string(),
+                        event.inner.weight.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        Some(&BribeClaimed::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<BribeClaimed>() {
+                let meta = chain::log_meta(log)?;
+                let tx_hash = log
+                    .transaction_hash
+                    .context("log without transaction hash")?;
+                let tx = chain::transaction(provider, tx_hash).await?;
+                // Only direct EOA calls can be decoded; claims routed through
+                // another contract are stored without epoch and region.
+                let (epoch, region_id) = match claimBribeCall::abi_decode(&tx.input, true) {
+                    Ok(call) => (
+                        Some(chain::u256_to_i64(call._epoch)?),
+                        Some(chain::u256_to_i64(call._regionId)?),
+                    ),
+                    Err(_) => (None, None),
+                };
+                println!(
+                    "Found BribeClaimed: voter={:?}, amount={}, epoch={:?}, region={:?}",
+                    event.inner.voter, event.inner.amount, epoch, region_id
+                );
+                voter_repo
+                    .record_bribe_claim(
+                        &mut *conn,
+                        &meta,
+                        epoch,
+                        region_id,
+                        event.inner.voter.to_string(),
+                        event.inner.amount.to_string(),
+                    )
+                    .await?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,94 @@
+use crate::events::DomainEvent;
+use crate::indexer::chain::{self, ChainProvider};
+use crate::indexer::contract::{TroopsEnlisted, WarDeclared, WarResult};
+use crate::repositories::war_repo::WarRepository;
+use crate::utils::epoch::epoch_at;
+use alloy::{rpc::types::Log, sol_types::SolEvent};
+use anyhow::Result;
+use sqlx::PgConnection;
+
+pub async fn handle_log(
+    provider: &ChainProvider,
+    war_repo: &WarRepository,
+    conn: &mut PgConnection,
+    log: &Log,
+) -> Result<Option<DomainEvent>> {
+    match log.topic0() {
+        Some(&WarDeclared::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<WarDeclared>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found WarDeclared: epoch={}, attacker={}, defender={}",
+                    event.inner.epoch, event.inner.attacker, event.inner.defender
+                );
+                war_repo
+                    .record_war_declared(
+                        &mut *conn,
+                        &meta,
+                        chain::u256_to_i64(event.inner.epoch)?,
+                        chain::u256_to_i64(event.inner.attacker)?,
+                        chain::u256_to_i64(event.inner.defender)?,
+                    )
+                    .await?;
+            }
+        }
+        Some(&TroopsEnlisted::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<TroopsEnlisted>() {
+                let meta = chain::log_meta(log)?;
+                // TroopsEnlisted has no epoch field; WarTheater uses the voter's epoch.
+                let epoch = epoch_at(chain::block_timestamp(provider, log).await?);
+                println!(
+                    "Found TroopsEnlisted: epoch={}, user={:?}, region={}, amount={}, attack={}",
+                    epoch,
+                    event.inner.user,
+                    event.inner.regionId,
+                    event.inner.amount,
+                    event.inner.isAttack
+                );
+                war_repo
+                    .record_troops_enlisted(
+                        &mut *conn,
+                        &meta,
+                        epoch as i64,
+                        chain::u256_to_i64(event.inner.regionId)?,
+                        event.inner.user.to_string(),
+                        event.inner.amount.to_string(),
+                        event.inner.isAttack,
+                    )
+                    .await?;
+            }
+        }
+        Some(&WarResult::SIGNATURE_HASH) => {
+            if let Ok(event) = log.log_decode::<WarResult>() {
+                let meta = chain::log_meta(log)?;
+                println!(
+                    "Found WarResult: epoch={}, attacker={}, defender={}, success={}",
+                    event.inner.epoch,
+                    event.inner.attacker,
+                    event.inner.defender,
+                    event.inner.success
+                );
+                let epoch = chain::u256_to_i64(event.inner.epoch)?;
+                let attacker_region = chain::u256_to_i64(event.inner.attacker)?;
+                war_repo
+                    .record_war_result(
+                        &mut *conn,
+                        &meta,
+                        epoch,
+                        attacker_region,
+                        event.inner.success,
+                    )
+                    .await?;
+                return Ok(Some(DomainEvent::WarResult {
+                    epoch,
+                    attacker_region,
+                    defender_region: chain::u256_to_i64(event.inner.defender)?,
+                    success: event.inner.success,
+                }));
+            }
+        }
+        _ => {}
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,76 @@
+use alloy::transports::{RpcError, TransportErrorKind};
+
+/// Successful chunks needed before the window is allowed to grow again.
+const GROW_AFTER: u32 = 5;
+
+/// JSON-RPC "limit exceeded" code, used by geth-style nodes and most providers.
+const LIMIT_EXCEEDED: i64 = -32005;
+
+/// Fragments of the messages providers send when an `eth_getLogs` range or
+/// response is too large. There is no standard error, so this is best effort.
+const TOO_LARGE_HINTS: &[&str] = &[
+    "query returned more than",
+    "response size",
+    "too many",
+    "too large",
+    "block range",
+    "range is too",
+    "limit exceeded",
+    "exceed maximum",
+    "exceeds the limit",
+];
+
+/// Number of blocks requested per `eth_getLogs` call. Halves whenever the
+/// provider rejects a range as too large and doubles back towards `max` after
+/// a run of successful chunks.
+#[derive(Debug)]
+pub struct BlockWindow {
+    size: u64,
+    max: u64,
+    successes: u32,
+}
+
+impl BlockWindow {
+    pub fn new(max: u64) -> Self {
+        let max = max.max(1);
+        Self {
+            size: max,
+            max,
+            successes: 0,
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Halves the window. Returns false if it is already a single block.
+    pub fn shrink(&mut self) -> bool {
+        self.successes = 0;
+        if self.size == 1 {
+            return false;
+        }
+        self.size /= 2;
+        true
+    }
+
+    pub fn grow(&mut self) {
+        self.successes += 1;
+        if self.successes >= GROW_AFTER {
+            self.successes = 0;
+            self.size = (self.size * 2).min(self.max);
+        }
+    }
+}
+
+/// Whether a `get_logs` failure means the range should be split rather than retried.
+pub fn is_range_too_large(err: &RpcError<TransportErrorKind>) -> bool {
+    if let RpcError::ErrorResp(payload) = err
+        && payload.code == LIMIT_EXCEEDED
+    {
+        return true;
+    }
+
+    let message = err.to_string().to_lowercase();
+    TOO_LARGE_HINTS.iter().any(|hint| message.contains(hint))
+}
@@ -0,0 +1,10 @@
+pub mod config;
+pub mod error;
+pub mod events;
+pub mod game;
+pub mod handlers;
+pub mod indexer;
+pub mod models;
+pub mod repositories;
+pub mod state;
+pub mod utils;
@@ -1,24 +1,34 @@
-mod config;
-mod error;
-mod handlers;
-mod indexer;
-mod models;
-mod repositories;
-mod state;
-mod utils;
-
-use crate::config::Config;
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    routing::{get, post, put},
+};
 use sqlx::postgres::PgPoolOptions;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
+use server::config::Config;
+use server::game::chat::ChatHub;
+use server::game::map::Maps;
+use server::game::moderation::ChatFilter;
+use server::game::rooms::Rooms;
+use server::game::sessions::Sessions;
+use server::indexer::listener::{ContractKind, IndexedContract};
+use server::{events, handlers, indexer, state};
+
 #[tokio::main]
 async fn main() {
     // Load config
-    let config = Config::from_env();
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid configuration: {:#}", e);
+            std::process::exit(1);
+        }
+    };
     tracing_subscriber::fmt::init();
 
     // Database Connection
@@ -34,39 +44,118 @@ async fn main() {
         .await
         .expect("Failed to run migrations");
 
+    // Collision maps for move validation
+    let maps = Maps::load(config.maps_dir.as_ref()).expect("Failed to load scene maps");
+
     // Initialize State
-    let clients: state::Clients = Arc::new(Mutex::new(HashMap::new()));
-    let players: state::Players = Arc::new(Mutex::new(HashMap::new()));
+    let maps = Arc::new(maps);
+    let rooms = Rooms::new(
+        maps.clone(),
+        pool.clone(),
+        config.room_capacity,
+        config.tick_rate,
+        Duration::from_secs(config.position_save_secs),
+    );
+
+    let chat = ChatHub::new(pool.clone(), ChatFilter::new(&config));
+    let sessions = Sessions::new(
+        rooms.clone(),
+        chat.clone(),
+        Duration::from_secs(config.resume_grace_secs),
+    );
 
     let app_state = state::AppState {
-        clients,
-        players,
+        rooms,
+        sessions,
+        chat,
         db: pool.clone(),
+        nonces: Arc::new(Mutex::new(HashMap::new())),
+        config: config.clone(),
+        maps,
     };
 
+    // Chain events flow from the indexer to the websocket handlers
+    let event_bus = events::event_bus();
+    tokio::spawn(handlers::ws::forward_domain_events(
+        app_state.clone(),
+        event_bus.subscribe(),
+    ));
+
     // Spawn Indexer
     let indexer_db = pool.clone();
     let rpc_url = config.rpc_url.clone();
+    let confirmations = config.indexer_confirmations;
+    let max_block_range = config.indexer_max_block_range;
 
     // Collect all addresses from config
-    let contract_addresses = vec![
-        config.MOCK_MANTLE_ADDRESS.clone(),
-        config.OLIG_TOKEN_ADDRESS.clone(),
-        config.GAMESTORE_ADDRESS.clone(),
-        config.VEOLIG_ADDRESS.clone(),
-        config.OLIG_VOTER_ADDRESS.clone(),
-        config.REGION_FARM_ADDRESS.clone(),
-        config.WAR_THEATER_ADDRESS.clone(),
+    let mut contracts = vec![
+        (ContractKind::MockMantle, config.MOCK_MANTLE_ADDRESS.clone()),
+        (ContractKind::OligToken, config.OLIG_TOKEN_ADDRESS.clone()),
+        (ContractKind::GameStore, config.GAMESTORE_ADDRESS.clone()),
+        (ContractKind::VeOligarchy, config.VEOLIG_ADDRESS.clone()),
+        (
+            ContractKind::OligarchyVoter,
+            config.OLIG_VOTER_ADDRESS.clone(),
+        ),
+        (ContractKind::RegionFarm, config.REGION_FARM_ADDRESS.clone()),
+        (ContractKind::WarTheater, config.WAR_THEATER_ADDRESS.clone()),
     ];
+    if let Some(addr) = &config.REGION_POLITICS_ADDRESS {
+        contracts.push((ContractKind::RegionPolitics, addr.clone()));
+    }
+    if let Some(addr) = &config.LAND_GENESIS_ADDRESS {
+        contracts.push((ContractKind::LandGenesis, addr.clone()));
+    }
+
+    let contracts = contracts
+        .into_iter()
+        .map(|(kind, address)| IndexedContract {
+            kind,
+            start_block: config.start_block(&address),
+            address,
+        })
+        .collect();
 
     tokio::spawn(async move {
-        indexer::listener::run_indexer(indexer_db, rpc_url, contract_addresses).await;
+        indexer::listener::run_indexer(
+            indexer_db,
+            rpc_url,
+            contracts,
+            confirmations,
+            max_block_range,
+            event_bus,
+        )
+        .await;
     });
 
     // Setup Router
     let app = Router::new()
         .route("/ws", get(handlers::ws::ws_handler))
+        .route("/api/auth/nonce", get(handlers::auth::nonce))
+        .route("/api/auth/verify", post(handlers::auth::verify))
+        .route("/api/players/:wallet", get(handlers::players::get_player))
+        .route("/api/regions", get(handlers::regions::list_regions))
+        .route(
+            "/api/leaderboards/:board",
+            get(handlers::leaderboards::get_leaderboard),
+        )
+        .route(
+            "/api/regions/:id/epochs/:epoch",
+            get(handlers::regions::get_region_epoch),
+        )
+        .route("/api/admin/sanctions", get(handlers::admin::list_sanctions))
+        .route(
+            "/api/admin/sanctions/:wallet/:kind",
+            put(handlers::admin::set_sanction).delete(handlers::admin::lift_sanction),
+        )
         .with_state(app_state)
+        // The frontend is served from its own origin
+        .layer(
+            CorsLayer::new()
+                .allow_origin(config.frontend_origin.clone())
+                .allow_methods(Any)
+                .allow_headers(Any),
+        )
         .layer(TraceLayer::new_for_http());
 
     // Run Server
@@ -74,5 +163,10 @@ async fn main() {
     println!("Server started at ws://{}", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
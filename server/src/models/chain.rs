@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Position of an indexed log on chain; every event row carries one.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LogMeta {
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+}
+
+/// How far the indexer got for one contract kind at an address.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IndexerCursor {
+    pub contract_address: String,
+    pub contract_kind: String,
+    pub start_block: i64,
+    pub last_processed_block: i64,
+}
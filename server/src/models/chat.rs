@@ -0,0 +1,65 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+pub const GLOBAL_CHANNEL: &str = "global";
+pub const WHISPER_CHANNEL: &str = "whisper";
+
+/// A chat line as stored and as sent to protocol 2 clients.
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatEntry {
+    pub channel: String,
+    /// Session id of the sender, which is their player id.
+    #[sqlx(rename = "sender_id")]
+    pub from: String,
+    #[sqlx(rename = "sender_wallet")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<String>,
+    /// Recipient wallet of a whisper.
+    #[sqlx(rename = "recipient_wallet")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    pub message: String,
+    pub sent_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Channels a client may join by name, open only to members: `region:<id>`
+/// or `guild:<name>`. `global` and the scene channel are joined
+/// automatically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberChannel {
+    Region(i64),
+    /// Lowercased, as guild names match ignoring case. Members are the
+    /// guild's governor candidates and the wallets that voted for them.
+    Guild(String),
+}
+
+impl MemberChannel {
+    pub fn name(&self) -> String {
+        match self {
+            MemberChannel::Region(id) => format!("region:{}", id),
+            MemberChannel::Guild(name) => format!("guild:{}", name),
+        }
+    }
+}
+
+pub fn joinable_channel(channel: &str) -> Result<MemberChannel> {
+    match channel.split_once(':') {
+        Some(("region", id)) if let Ok(id) = id.parse::<u32>() => {
+            Ok(MemberChannel::Region(id.into()))
+        }
+        Some(("guild", name))
+            if (1..=32).contains(&name.len())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') =>
+        {
+            Ok(MemberChannel::Guild(name.to_lowercase()))
+        }
+        _ => bail!("unknown chat channel: {}", channel),
+    }
+}
+
+pub fn scene_channel(scene: &str) -> String {
+    format!("scene:{}", scene)
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct FarmPosition {
+    pub wallet_address: String,
+    pub pid: i64,
+    pub staked: String,
+    pub total_deposited: String,
+    pub total_withdrawn: String,
+    pub total_tax_paid: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct PoolTvl {
+    pub pid: i64,
+    pub tvl: String,
+    pub stakers: i64,
+}
@@ -1,3 +1,6 @@
+use crate::events::DomainEvent;
+use crate::models::chat::ChatEntry;
+use crate::models::store::InventoryItem;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -8,6 +11,33 @@ pub struct Player {
     pub y: f32,
     pub anim: String,
     pub scene: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<String>,
+    /// Names of GameStore items the wallet owns, shown to other players.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cosmetics: Vec<String>,
+}
+
+/// A player's position in a snapshot.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlayerState {
+    pub id: String,
+    pub x: f32,
+    pub y: f32,
+    pub anim: String,
+    pub scene: String,
+}
+
+impl From<&Player> for PlayerState {
+    fn from(player: &Player) -> Self {
+        Self {
+            id: player.id.clone(),
+            x: player.x,
+            y: player.y,
+            anim: player.anim.clone(),
+            scene: player.scene.clone(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -27,10 +57,145 @@ pub enum GameMessage {
         anim: String,
         scene: String,
     },
+    /// Another player came within the interest radius.
+    #[serde(rename = "enterView")]
+    EnterView { player: Player },
+    /// Another player went out of the interest radius.
+    #[serde(rename = "leaveView")]
+    LeaveView { id: String },
+    /// Sent when the client is put into a room, before its `currentPlayers`.
+    #[serde(rename = "roomJoined")]
+    RoomJoined { scene: String, instance: u32 },
+    /// Sent to a client whose move was rejected or cut short, with the
+    /// position the server kept.
+    #[serde(rename = "correction")]
+    Correction { x: f32, y: f32, scene: String },
+    /// Players of a room that moved during one server tick. `seq` counts up
+    /// per room so clients can order snapshots and interpolate between them.
+    #[serde(rename = "snapshot")]
+    Snapshot {
+        seq: u64,
+        scene: String,
+        players: Vec<PlayerState>,
+    },
     #[serde(rename = "userDisconnected")]
     UserDisconnected { id: String },
+    /// Scene chat of protocol 1, still accepted from and sent to clients
+    /// that did not ask for protocol 2.
     #[serde(rename = "chat")]
     Chat { id: String, message: String },
-    #[serde(rename = "welcome")]
-    Welcome { id: String },
+    /// Protocol 2: sends to `scene`, `global` or a joined `region:<id>` or
+    /// `guild:<name>` channel.
+    #[serde(rename = "chatSend")]
+    ChatSend { channel: String, message: String },
+    /// Protocol 2: joins a region or guild channel. Only members by their
+    /// indexed history may join; see `MemberChannel`.
+    #[serde(rename = "chatJoin")]
+    ChatJoin { channel: String },
+    #[serde(rename = "chatLeave")]
+    ChatLeave { channel: String },
+    /// Protocol 2: a direct message to every session of a wallet.
+    #[serde(rename = "whisper")]
+    Whisper { to: String, message: String },
+    #[serde(rename = "chatMessage")]
+    ChatMessage(ChatEntry),
+    /// Recent messages of a channel, oldest first, sent when joining it.
+    #[serde(rename = "chatHistory")]
+    ChatHistory {
+        channel: String,
+        messages: Vec<ChatEntry>,
+    },
+    /// A chat message or request the server refused.
+    #[serde(rename = "chatRejected")]
+    ChatRejected { reason: String },
+    /// `resume_token` lets the client reconnect to this session after a
+    /// dropped connection; `resumed` is set when it just did. `protocol` is
+    /// the message protocol the session speaks.
+    #[serde(rename = "welcome", rename_all = "camelCase")]
+    Welcome {
+        id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wallet: Option<String>,
+        resume_token: String,
+        resumed: bool,
+        protocol: u8,
+    },
+    #[serde(rename = "inventory")]
+    Inventory { items: Vec<InventoryItem> },
+    #[serde(rename = "warResult", rename_all = "camelCase")]
+    WarResult {
+        epoch: i64,
+        attacker_region: i64,
+        defender_region: i64,
+        success: bool,
+    },
+    #[serde(rename = "governorElected", rename_all = "camelCase")]
+    GovernorElected {
+        region_id: i64,
+        epoch: i64,
+        governor: String,
+        votes: String,
+    },
+    #[serde(rename = "bribeSeized", rename_all = "camelCase")]
+    BribeSeized {
+        epoch: i64,
+        from_region: i64,
+        to_region: i64,
+        amount: String,
+    },
+    #[serde(rename = "landMinted", rename_all = "camelCase")]
+    LandMinted {
+        token_id: i64,
+        tier: i64,
+        owner: String,
+    },
+}
+
+impl From<DomainEvent> for GameMessage {
+    fn from(event: DomainEvent) -> Self {
+        match event {
+            DomainEvent::WarResult {
+                epoch,
+                attacker_region,
+                defender_region,
+                success,
+            } => GameMessage::WarResult {
+                epoch,
+                attacker_region,
+                defender_region,
+                success,
+            },
+            DomainEvent::GovernorElected {
+                region_id,
+                epoch,
+                governor,
+                votes,
+            } => GameMessage::GovernorElected {
+                region_id,
+                epoch,
+                governor,
+                votes,
+            },
+            DomainEvent::BribeSeized {
+                epoch,
+                from_region,
+                to_region,
+                amount,
+            } => GameMessage::BribeSeized {
+                epoch,
+                from_region,
+                to_region,
+                amount,
+            },
+            DomainEvent::LandMinted {
+                token_id,
+                tier,
+                owner,
+            } => GameMessage::LandMinted {
+                token_id,
+                tier,
+                owner,
+            },
+        }
+    }
 }
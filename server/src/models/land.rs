@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+pub const TIER_COMMON: i64 = 1;
+pub const TIER_RARE: i64 = 2;
+pub const TIER_LEGENDARY: i64 = 3;
+
+/// Boost percent of a tier, as configured in the `LandGenesis` constructor.
+pub fn vote_multiplier(tier: i64) -> i64 {
+    match tier {
+        TIER_COMMON => 1,
+        TIER_RARE => 3,
+        TIER_LEGENDARY => 5,
+        _ => 0,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct LandToken {
+    pub token_id: i64,
+    pub tier: i64,
+    pub vote_multiplier: i64,
+    pub owner: String,
+    pub minted_by: String,
+    pub mint_block: i64,
+    pub mint_tx_hash: String,
+}
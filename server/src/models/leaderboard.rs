@@ -0,0 +1,50 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// `epoch` of the all-time rows in `leaderboard_scores`.
+pub const ALL_TIME: i64 = -1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Leaderboard {
+    Stakers,
+    Voters,
+    Warlords,
+    Governors,
+    Bribers,
+}
+
+impl Leaderboard {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Leaderboard::Stakers => "stakers",
+            Leaderboard::Voters => "voters",
+            Leaderboard::Warlords => "warlords",
+            Leaderboard::Governors => "governors",
+            Leaderboard::Bribers => "bribers",
+        }
+    }
+}
+
+impl FromStr for Leaderboard {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "stakers" => Leaderboard::Stakers,
+            "voters" => Leaderboard::Voters,
+            "warlords" => Leaderboard::Warlords,
+            "governors" => Leaderboard::Governors,
+            "bribers" => Leaderboard::Bribers,
+            _ => bail!("unknown leaderboard: {}", s),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct LeaderboardEntry {
+    pub rank: i64,
+    pub wallet_address: String,
+    pub score: String,
+}
@@ -1,2 +1,15 @@
+pub mod chain;
+pub mod chat;
+pub mod farm;
 pub mod game;
+pub mod land;
+pub mod leaderboard;
+pub mod moderation;
+pub mod politics;
+pub mod position;
+pub mod store;
+pub mod token;
 pub mod user;
+pub mod ve;
+pub mod voter;
+pub mod war;
@@ -0,0 +1,66 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SanctionKind {
+    /// The wallet can play but not chat.
+    Mute,
+    /// The wallet cannot connect.
+    Ban,
+}
+
+impl SanctionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SanctionKind::Mute => "mute",
+            SanctionKind::Ban => "ban",
+        }
+    }
+}
+
+impl FromStr for SanctionKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "mute" => SanctionKind::Mute,
+            "ban" => SanctionKind::Ban,
+            _ => bail!("unknown sanction: {}", s),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow)]
+pub struct Sanction {
+    pub wallet_address: String,
+    pub kind: String,
+    pub reason: Option<String>,
+    /// Admin wallet that set it.
+    pub issued_by: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Sanction {
+    pub fn is_active(&self) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| expires_at > chrono::Utc::now())
+    }
+
+    /// Tells the sanctioned player why and for how long.
+    pub fn describe(&self) -> String {
+        let mut text = match self.kind.as_str() {
+            "ban" => "banned".to_string(),
+            _ => "muted".to_string(),
+        };
+        if let Some(expires_at) = self.expires_at {
+            text.push_str(&format!(" until {}", expires_at.to_rfc3339()));
+        }
+        if let Some(reason) = &self.reason {
+            text.push_str(&format!(": {}", reason));
+        }
+        text
+    }
+}
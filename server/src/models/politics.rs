@@ -0,0 +1,78 @@
+use crate::utils::epoch::epoch_start;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct Candidate {
+    pub region_id: i64,
+    pub epoch: i64,
+    pub candidate: String,
+    pub guild_name: String,
+    pub total_votes: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct ElectionVote {
+    pub region_id: i64,
+    pub epoch: i64,
+    pub voter: String,
+    pub candidate: String,
+    pub weight: String,
+    pub block_number: i64,
+    pub tx_hash: String,
+}
+
+/// Winner of the election held in `epoch`; rules during `epoch + 1`
+/// unless ousted by a revolution.
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct Governor {
+    pub region_id: i64,
+    pub epoch: i64,
+    pub governor: String,
+    pub guild_name: String,
+    pub votes: String,
+    pub ousted: bool,
+    pub ousted_epoch: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct Revolution {
+    pub region_id: i64,
+    pub epoch: i64,
+    pub provocateur: String,
+    pub started_at: i64,
+    pub total_support: String,
+    pub executed: bool,
+    pub ousted_governor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RevolutionOutcome {
+    Active,
+    Succeeded,
+    Failed,
+}
+
+impl Revolution {
+    /// A failed revolution never emits an event: `executeRevolution` reverts
+    /// below threshold, so it simply stays unexecuted until its epoch ends.
+    pub fn outcome(&self, now: u64) -> RevolutionOutcome {
+        if self.executed {
+            RevolutionOutcome::Succeeded
+        } else if now < epoch_start(self.epoch as u64 + 1) {
+            RevolutionOutcome::Active
+        } else {
+            RevolutionOutcome::Failed
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct RevolutionSupporter {
+    pub region_id: i64,
+    pub epoch: i64,
+    pub supporter: String,
+    pub weight: String,
+    pub block_number: i64,
+    pub tx_hash: String,
+}
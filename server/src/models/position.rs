@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow)]
+pub struct PlayerPosition {
+    pub wallet_address: String,
+    pub scene: String,
+    pub x: f32,
+    pub y: f32,
+    pub anim: String,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct StoreItem {
+    pub item_id: i64,
+    pub name: String,
+    pub price: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+pub struct InventoryItem {
+    pub item_id: i64,
+    pub name: String,
+    pub quantity: i64,
+    pub total_spent: String,
+}
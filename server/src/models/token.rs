@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct TokenBalance {
+    pub token_address: String,
+    pub wallet_address: String,
+    pub balance: String,
+}
@@ -1,10 +1,9 @@
-use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
 pub struct User {
     pub wallet_address: String,
-    pub balance: BigDecimal,
+    pub balance: String, // total farm stake, read as NUMERIC::TEXT
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -0,0 +1,37 @@
+use alloy::primitives::U256;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Mirrors `VeOligarchy.MAX_LOCK` (365 days).
+pub const MAX_LOCK: u64 = 365 * 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct VeLock {
+    pub wallet_address: String,
+    pub amount: String,
+    pub unlock_time: i64,
+}
+
+impl VeLock {
+    /// veOLIG balance at `timestamp`, computed like `VeOligarchy.balanceOf`:
+    /// the locked amount decays linearly to zero at `unlock_time`.
+    pub fn voting_power_at(&self, timestamp: u64) -> Result<U256> {
+        let unlock_time = self.unlock_time as u64;
+        if timestamp >= unlock_time {
+            return Ok(U256::ZERO);
+        }
+
+        let amount = U256::from_str(&self.amount)?;
+        let remaining = U256::from(unlock_time - timestamp);
+        Ok(amount * remaining / U256::from(MAX_LOCK))
+    }
+}
+
+/// Vote weight after the LandGenesis boost, like `OligarchyVoter.vote`.
+pub fn boosted_vote_weight(power: U256, boost_percent: i64) -> U256 {
+    if boost_percent <= 0 {
+        return power;
+    }
+    power + power * U256::from(boost_percent as u64) / U256::from(100)
+}
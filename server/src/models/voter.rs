@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+// uint256 amounts are read as `NUMERIC::TEXT` and kept as decimal strings.
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct BribeDeposit {
+    pub epoch: i64,
+    pub region_id: i64,
+    pub depositor: String,
+    pub amount: String,
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct BribeSeizure {
+    pub epoch: i64,
+    pub from_region: i64,
+    pub to_region: i64,
+    pub amount: String,
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct Vote {
+    pub epoch: i64,
+    pub region_id: i64,
+    pub voter: String,
+    pub weight: String,
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// `BribeClaimed` only carries voter and amount; epoch and region come from
+/// the `claimBribe` calldata and are `None` when it could not be decoded.
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct BribeClaim {
+    pub epoch: Option<i64>,
+    pub region_id: Option<i64>,
+    pub voter: String,
+    pub amount: String,
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
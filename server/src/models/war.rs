@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct War {
+    pub epoch: i64,
+    pub attacker_region: i64,
+    pub defender_region: i64,
+    pub resolved: bool,
+    pub attacker_won: Option<bool>,
+    pub declared_block: i64,
+    pub declared_tx_hash: String,
+    pub resolved_block: Option<i64>,
+    pub resolved_tx_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct WarEnlistment {
+    pub epoch: i64,
+    pub region_id: i64,
+    pub wallet_address: String,
+    pub amount: String,
+    pub is_attack: bool,
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+}
+
+/// Troops one wallet committed to one side of a war.
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct WarContribution {
+    pub epoch: i64,
+    pub attacker_region: i64,
+    pub defender_region: i64,
+    pub is_attack: bool,
+    pub amount: String,
+    pub attacker_won: Option<bool>,
+}
+
+/// Resolved wars counted from one region's point of view, as attacker and as defender.
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct RegionWarRecord {
+    pub region_id: i64,
+    pub attacks_won: i64,
+    pub attacks_lost: i64,
+    pub defenses_held: i64,
+    pub defenses_lost: i64,
+}
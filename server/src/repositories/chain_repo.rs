@@ -0,0 +1,183 @@
+use crate::models::chain::{IndexerCursor, LogMeta};
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool, Row};
+
+/// Indexer cursors and the hashes of indexed blocks used to detect reorgs.
+pub struct ChainRepository {
+    pool: PgPool,
+}
+
+impl ChainRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Creates the cursor of a newly configured contract so it is indexed from
+    /// `start_block`. Returns false if the contract already had a cursor.
+    pub async fn ensure_cursor(&self, address: &str, kind: &str, start_block: i64) -> Result<bool> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO indexer_cursors
+                (contract_address, contract_kind, start_block, last_processed_block)
+            VALUES ($1, $2, $3, GREATEST($3 - 1, 0))
+            ON CONFLICT (contract_address, contract_kind) DO NOTHING
+            "#,
+        )
+        .bind(address)
+        .bind(kind)
+        .bind(start_block)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        Ok(inserted > 0)
+    }
+
+    pub async fn find_cursors(&self, addresses: &[String]) -> Result<Vec<IndexerCursor>> {
+        let cursors = sqlx::query(
+            r#"
+            SELECT contract_address, contract_kind, start_block, last_processed_block
+            FROM indexer_cursors
+            WHERE contract_address = ANY($1)
+            "#,
+        )
+        .bind(addresses)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| IndexerCursor {
+            contract_address: row.get("contract_address"),
+            contract_kind: row.get("contract_kind"),
+            start_block: row.get("start_block"),
+            last_processed_block: row.get("last_processed_block"),
+        })
+        .collect();
+
+        Ok(cursors)
+    }
+
+    /// Moves the cursors of the given (address, kind) pairs, passed as two
+    /// parallel lists.
+    pub async fn set_last_processed_block(
+        &self,
+        conn: &mut PgConnection,
+        addresses: &[String],
+        kinds: &[String],
+        block: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE indexer_cursors c
+            SET last_processed_block = $3, updated_at = NOW()
+            FROM UNNEST($1::TEXT[], $2::TEXT[]) AS t(contract_address, contract_kind)
+            WHERE c.contract_address = t.contract_address
+              AND c.contract_kind = t.contract_kind
+            "#,
+        )
+        .bind(addresses)
+        .bind(kinds)
+        .bind(block)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn record_block(
+        &self,
+        conn: &mut PgConnection,
+        block: i64,
+        hash: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO indexed_blocks (block_number, block_hash)
+            VALUES ($1, $2)
+            ON CONFLICT (block_number) DO UPDATE SET block_hash = $2, created_at = NOW()
+            "#,
+        )
+        .bind(block)
+        .bind(hash)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Claims a log for application by one contract kind; returns false if
+    /// that kind applied it before.
+    pub async fn mark_applied(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        kind: &str,
+    ) -> Result<bool> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO applied_events (tx_hash, log_index, block_number, contract_kind)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tx_hash, log_index, contract_kind) DO NOTHING
+            "#,
+        )
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .bind(meta.block_number)
+        .bind(kind)
+        .execute(conn)
+        .await?
+        .rows_affected();
+
+        Ok(inserted > 0)
+    }
+
+    pub async fn block_hash(&self, block: i64) -> Result<Option<String>> {
+        let hash = sqlx::query("SELECT block_hash FROM indexed_blocks WHERE block_number = $1")
+            .bind(block)
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|row| row.get("block_hash"));
+
+        Ok(hash)
+    }
+
+    /// All recorded blocks at or below `block`, oldest first.
+    pub async fn blocks_up_to(&self, block: i64) -> Result<Vec<(i64, String)>> {
+        let blocks = sqlx::query(
+            "SELECT block_number, block_hash FROM indexed_blocks WHERE block_number <= $1 ORDER BY block_number",
+        )
+        .bind(block)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| (row.get("block_number"), row.get("block_hash")))
+        .collect();
+
+        Ok(blocks)
+    }
+
+    pub async fn rollback_after(&self, conn: &mut PgConnection, block: i64) -> Result<()> {
+        sqlx::query("DELETE FROM indexed_blocks WHERE block_number > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query("DELETE FROM applied_events WHERE block_number > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        // Contracts still backfilling below the fork keep their position
+        sqlx::query(
+            r#"
+            UPDATE indexer_cursors
+            SET last_processed_block = $1, updated_at = NOW()
+            WHERE last_processed_block > $1
+            "#,
+        )
+        .bind(block)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use crate::models::chat::{ChatEntry, WHISPER_CHANNEL};
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub struct ChatRepository {
+    pool: PgPool,
+}
+
+impl ChatRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record_message(&self, entry: &ChatEntry) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO chat_messages
+                (channel, sender_id, sender_wallet, recipient_wallet, message, sent_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(&entry.channel)
+        .bind(&entry.from)
+        .bind(&entry.wallet)
+        .bind(&entry.to)
+        .bind(&entry.message)
+        .bind(entry.sent_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Most recent messages of a channel, oldest first.
+    pub async fn find_history(&self, channel: &str, limit: i64) -> Result<Vec<ChatEntry>> {
+        let entries = sqlx::query_as::<_, ChatEntry>(
+            r#"
+            SELECT channel, sender_id, sender_wallet, recipient_wallet, message, sent_at
+            FROM (
+                SELECT * FROM chat_messages
+                WHERE channel = $1
+                ORDER BY sent_at DESC, id DESC
+                LIMIT $2
+            ) recent
+            ORDER BY sent_at, id
+            "#,
+        )
+        .bind(channel)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Most recent whispers sent or received by a wallet, oldest first.
+    pub async fn find_whispers(&self, wallet: &str, limit: i64) -> Result<Vec<ChatEntry>> {
+        let entries = sqlx::query_as::<_, ChatEntry>(
+            r#"
+            SELECT channel, sender_id, sender_wallet, recipient_wallet, message, sent_at
+            FROM (
+                SELECT * FROM chat_messages
+                WHERE channel = $1 AND (sender_wallet = $2 OR recipient_wallet = $2)
+                ORDER BY sent_at DESC, id DESC
+                LIMIT $3
+            ) recent
+            ORDER BY sent_at, id
+            "#,
+        )
+        .bind(WHISPER_CHANNEL)
+        .bind(wallet)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+}
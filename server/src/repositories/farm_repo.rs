@@ -0,0 +1,223 @@
+use crate::models::chain::LogMeta;
+use crate::models::farm::{FarmPosition, PoolTvl};
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool, Row};
+
+pub struct FarmRepository {
+    pool: PgPool,
+}
+
+impl FarmRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record_deposit(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        wallet_address: String,
+        pid: i64,
+        epoch: i64,
+        amount: String,
+    ) -> Result<()> {
+        if !self
+            .record_event(
+                &mut *conn,
+                meta,
+                &wallet_address,
+                pid,
+                epoch,
+                true,
+                &amount,
+                "0",
+            )
+            .await?
+        {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO farm_positions (wallet_address, pid, staked, total_deposited, updated_at)
+            VALUES ($1, $2, $3::numeric, $3::numeric, NOW())
+            ON CONFLICT (wallet_address, pid)
+            DO UPDATE SET staked = farm_positions.staked + $3::numeric,
+                          total_deposited = farm_positions.total_deposited + $3::numeric,
+                          updated_at = NOW()
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(pid)
+        .bind(amount)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// `Withdraw.amount` is what the user received after the exit tax; the
+    /// stake shrinks by `amount + tax`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_withdraw(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        wallet_address: String,
+        pid: i64,
+        epoch: i64,
+        amount: String,
+        tax: String,
+    ) -> Result<()> {
+        if !self
+            .record_event(
+                &mut *conn,
+                meta,
+                &wallet_address,
+                pid,
+                epoch,
+                false,
+                &amount,
+                &tax,
+            )
+            .await?
+        {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO farm_positions
+                (wallet_address, pid, staked, total_withdrawn, total_tax_paid, updated_at)
+            VALUES ($1, $2, -($3::numeric + $4::numeric), $3::numeric + $4::numeric, $4::numeric, NOW())
+            ON CONFLICT (wallet_address, pid)
+            DO UPDATE SET staked = farm_positions.staked - ($3::numeric + $4::numeric),
+                          total_withdrawn = farm_positions.total_withdrawn + $3::numeric + $4::numeric,
+                          total_tax_paid = farm_positions.total_tax_paid + $4::numeric,
+                          updated_at = NOW()
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(pid)
+        .bind(amount)
+        .bind(tax)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Appends the raw log; returns false if it was already recorded.
+    #[allow(clippy::too_many_arguments)]
+    async fn record_event(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        wallet_address: &str,
+        pid: i64,
+        epoch: i64,
+        is_deposit: bool,
+        amount: &str,
+        tax: &str,
+    ) -> Result<bool> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO farm_events
+                (wallet_address, pid, epoch, is_deposit, amount, tax, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4, $5::numeric, $6::numeric, $7, $8, $9)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(pid)
+        .bind(epoch)
+        .bind(is_deposit)
+        .bind(amount)
+        .bind(tax)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        Ok(inserted > 0)
+    }
+
+    /// Drops logs after `block` and rebuilds every position from the rest.
+    pub async fn rollback_after(&self, conn: &mut PgConnection, block: i64) -> Result<()> {
+        sqlx::query("DELETE FROM farm_events WHERE block_number > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query("DELETE FROM farm_positions")
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO farm_positions
+                (wallet_address, pid, staked, total_deposited, total_withdrawn, total_tax_paid)
+            SELECT wallet_address, pid,
+                   SUM(CASE WHEN is_deposit THEN amount ELSE -(amount + tax) END),
+                   SUM(CASE WHEN is_deposit THEN amount ELSE 0 END),
+                   SUM(CASE WHEN is_deposit THEN 0 ELSE amount + tax END),
+                   SUM(tax)
+            FROM farm_events
+            GROUP BY wallet_address, pid
+            "#,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_positions(&self, wallet_address: &str) -> Result<Vec<FarmPosition>> {
+        let positions = sqlx::query_as::<_, FarmPosition>(
+            r#"
+            SELECT wallet_address, pid, staked::TEXT AS staked,
+                   total_deposited::TEXT AS total_deposited,
+                   total_withdrawn::TEXT AS total_withdrawn,
+                   total_tax_paid::TEXT AS total_tax_paid
+            FROM farm_positions
+            WHERE wallet_address = $1
+            ORDER BY pid
+            "#,
+        )
+        .bind(wallet_address)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(positions)
+    }
+
+    pub async fn pool_tvls(&self) -> Result<Vec<PoolTvl>> {
+        let tvls = sqlx::query_as::<_, PoolTvl>(
+            r#"
+            SELECT pid, SUM(staked)::TEXT AS tvl, COUNT(*) FILTER (WHERE staked > 0) AS stakers
+            FROM farm_positions
+            GROUP BY pid
+            ORDER BY pid
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tvls)
+    }
+
+    /// Current stake in one pool; positions are not kept per epoch.
+    pub async fn pool_tvl(&self, pid: i64) -> Result<String> {
+        let tvl: String = sqlx::query(
+            "SELECT COALESCE(SUM(staked), 0)::TEXT AS tvl FROM farm_positions WHERE pid = $1",
+        )
+        .bind(pid)
+        .fetch_one(&self.pool)
+        .await?
+        .get("tvl");
+
+        Ok(tvl)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::models::chain::LogMeta;
+use crate::models::land::{LandToken, vote_multiplier};
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool, Row};
+
+pub struct LandRepository {
+    pool: PgPool,
+}
+
+impl LandRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record_land_minted(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        token_id: i64,
+        tier: i64,
+        buyer: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO land_tokens
+                (token_id, tier, vote_multiplier, owner, minted_by, mint_block, mint_tx_hash, last_transfer_block)
+            VALUES ($1, $2, $3, $4, $4, $5, $6, $5)
+            ON CONFLICT (token_id) DO NOTHING
+            "#,
+        )
+        .bind(token_id)
+        .bind(tier)
+        .bind(vote_multiplier(tier))
+        .bind(buyer)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Logs every transfer and moves ownership of an already minted token.
+    /// The mint transfer precedes `LandMinted`, so its update matches no row.
+    pub async fn record_transfer(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        token_id: i64,
+        from: String,
+        to: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO land_transfers (token_id, from_address, to_address, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(token_id)
+        .bind(from)
+        .bind(&to)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE land_tokens
+            SET owner = $2, last_transfer_block = $3, updated_at = NOW()
+            WHERE token_id = $1 AND last_transfer_block <= $3
+            "#,
+        )
+        .bind(token_id)
+        .bind(to)
+        .bind(meta.block_number)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drops mints and transfers after `block` and restores each remaining
+    /// token's owner from its latest surviving transfer.
+    pub async fn rollback_after(&self, conn: &mut PgConnection, block: i64) -> Result<()> {
+        sqlx::query("DELETE FROM land_transfers WHERE block_number > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query("DELETE FROM land_tokens WHERE mint_block > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE land_tokens t
+            SET owner = COALESCE(x.to_address, t.minted_by),
+                last_transfer_block = COALESCE(x.block_number, t.mint_block),
+                updated_at = NOW()
+            FROM land_tokens t2
+            LEFT JOIN LATERAL (
+                SELECT to_address, block_number
+                FROM land_transfers
+                WHERE token_id = t2.token_id
+                ORDER BY block_number DESC, log_index DESC
+                LIMIT 1
+            ) x ON TRUE
+            WHERE t.token_id = t2.token_id AND t.last_transfer_block > $1
+            "#,
+        )
+        .bind(block)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_tokens_by_owner(&self, owner: &str) -> Result<Vec<LandToken>> {
+        let tokens = sqlx::query_as::<_, LandToken>(
+            r#"
+            SELECT token_id, tier, vote_multiplier, owner, minted_by, mint_block, mint_tx_hash
+            FROM land_tokens
+            WHERE owner = $1
+            ORDER BY token_id
+            "#,
+        )
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tokens)
+    }
+
+    /// Highest boost percent among the wallet's lands, like `LandGenesis.getMultiplier`.
+    pub async fn best_multiplier(&self, owner: &str) -> Result<i64> {
+        let multiplier: i64 = sqlx::query(
+            "SELECT COALESCE(MAX(vote_multiplier), 0)::BIGINT AS multiplier FROM land_tokens WHERE owner = $1",
+        )
+        .bind(owner)
+        .fetch_one(&self.pool)
+        .await?
+        .get("multiplier");
+
+        Ok(multiplier)
+    }
+}
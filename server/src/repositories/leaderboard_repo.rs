@@ -0,0 +1,50 @@
+use crate::models::leaderboard::{Leaderboard, LeaderboardEntry};
+use anyhow::Result;
+use sqlx::PgPool;
+
+/// Rankings read from the `leaderboard_scores` materialized view.
+pub struct LeaderboardRepository {
+    pool: PgPool,
+}
+
+impl LeaderboardRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Recomputes every board from the event tables. Runs concurrently, so
+    /// readers keep seeing the previous rankings until it completes.
+    pub async fn refresh(&self) -> Result<()> {
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY leaderboard_scores")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Top wallets of a board for `epoch`, or of all time for `ALL_TIME`.
+    /// Wallets without a positive score are not ranked.
+    pub async fn find_entries(
+        &self,
+        board: Leaderboard,
+        epoch: i64,
+        limit: i64,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let entries = sqlx::query_as::<_, LeaderboardEntry>(
+            r#"
+            SELECT RANK() OVER (ORDER BY score DESC) AS rank, wallet_address, score::TEXT AS score
+            FROM leaderboard_scores
+            WHERE board = $1 AND epoch = $2 AND score > 0
+            ORDER BY score DESC, wallet_address
+            LIMIT $3
+            "#,
+        )
+        .bind(board.as_str())
+        .bind(epoch)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+}
@@ -1 +1,15 @@
+pub mod chain_repo;
+pub mod chat_repo;
+pub mod farm_repo;
+pub mod land_repo;
+pub mod leaderboard_repo;
+pub mod moderation_repo;
+pub mod politics_repo;
+pub mod position_repo;
+pub mod region_repo;
+pub mod store_repo;
+pub mod token_repo;
 pub mod user_repo;
+pub mod ve_repo;
+pub mod voter_repo;
+pub mod war_repo;
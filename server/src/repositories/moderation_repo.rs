@@ -0,0 +1,89 @@
+use crate::models::moderation::{Sanction, SanctionKind};
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub struct ModerationRepository {
+    pool: PgPool,
+}
+
+impl ModerationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Mutes and bans of a wallet that have not expired.
+    pub async fn find_active(&self, wallet_address: &str) -> Result<Vec<Sanction>> {
+        let sanctions = sqlx::query_as::<_, Sanction>(
+            r#"
+            SELECT wallet_address, kind, reason, issued_by, created_at, expires_at
+            FROM chat_sanctions
+            WHERE wallet_address = $1 AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+        )
+        .bind(wallet_address)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sanctions)
+    }
+
+    pub async fn list_active(&self) -> Result<Vec<Sanction>> {
+        let sanctions = sqlx::query_as::<_, Sanction>(
+            r#"
+            SELECT wallet_address, kind, reason, issued_by, created_at, expires_at
+            FROM chat_sanctions
+            WHERE expires_at IS NULL OR expires_at > NOW()
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sanctions)
+    }
+
+    /// Sets a sanction, replacing any earlier one of the same kind.
+    pub async fn upsert_sanction(
+        &self,
+        wallet_address: &str,
+        kind: SanctionKind,
+        reason: Option<&str>,
+        issued_by: &str,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Sanction> {
+        let sanction = sqlx::query_as::<_, Sanction>(
+            r#"
+            INSERT INTO chat_sanctions (wallet_address, kind, reason, issued_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (wallet_address, kind)
+            DO UPDATE SET reason = $3, issued_by = $4, created_at = NOW(), expires_at = $5
+            RETURNING wallet_address, kind, reason, issued_by, created_at, expires_at
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(kind.as_str())
+        .bind(reason)
+        .bind(issued_by)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(sanction)
+    }
+
+    /// Lifts a sanction. Returns false if there was none.
+    pub async fn delete_sanction(&self, wallet_address: &str, kind: SanctionKind) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM chat_sanctions
+            WHERE wallet_address = $1 AND kind = $2
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(kind.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
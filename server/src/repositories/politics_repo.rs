@@ -0,0 +1,474 @@
+use crate::models::chain::LogMeta;
+use crate::models::politics::{Candidate, ElectionVote, Governor, Revolution, RevolutionSupporter};
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool, Row};
+
+pub struct PoliticsRepository {
+    pool: PgPool,
+}
+
+impl PoliticsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record_nomination(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        region_id: i64,
+        epoch: i64,
+        candidate: String,
+        guild_name: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO election_candidates (region_id, epoch, candidate, guild_name, block_number, tx_hash)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (region_id, epoch, candidate) DO NOTHING
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .bind(candidate)
+        .bind(guild_name)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Stores the ballot and adds its weight to the candidate's tally.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_election_vote(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        region_id: i64,
+        epoch: i64,
+        voter: String,
+        candidate: String,
+        weight: String,
+    ) -> Result<()> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO election_votes
+                (region_id, epoch, voter, candidate, weight, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4, $5::numeric, $6, $7, $8)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .bind(voter)
+        .bind(&candidate)
+        .bind(&weight)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        if inserted > 0 {
+            sqlx::query(
+                r#"
+                UPDATE election_candidates
+                SET total_votes = total_votes + $4::numeric
+                WHERE region_id = $1 AND epoch = $2 AND candidate = $3
+                "#,
+            )
+            .bind(region_id)
+            .bind(epoch)
+            .bind(candidate)
+            .bind(weight)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// The guild is not part of `GovernorElected`; it is copied from the nomination.
+    pub async fn record_governor_elected(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        region_id: i64,
+        epoch: i64,
+        governor: String,
+        votes: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO governors (region_id, epoch, governor, guild_name, votes, block_number, tx_hash)
+            VALUES (
+                $1, $2, $3,
+                COALESCE(
+                    (SELECT guild_name FROM election_candidates
+                     WHERE region_id = $1 AND epoch = $2 AND candidate = $3),
+                    ''
+                ),
+                $4::numeric, $5, $6
+            )
+            ON CONFLICT (region_id, epoch) DO NOTHING
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .bind(governor)
+        .bind(votes)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_revolution_started(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        region_id: i64,
+        epoch: i64,
+        provocateur: String,
+        started_at: i64,
+        provocateur_power: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO revolutions (region_id, epoch, provocateur, started_at, provocateur_power,
+                                     total_support, block_number, tx_hash)
+            VALUES ($1, $2, $3, $4, $5::numeric, $5::numeric, $6, $7)
+            ON CONFLICT (region_id, epoch) DO NOTHING
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .bind(provocateur)
+        .bind(started_at)
+        .bind(provocateur_power)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn record_revolution_support(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        region_id: i64,
+        epoch: i64,
+        supporter: String,
+        weight: String,
+    ) -> Result<()> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO revolution_supporters
+                (region_id, epoch, supporter, weight, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4::numeric, $5, $6, $7)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .bind(supporter)
+        .bind(&weight)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        if inserted > 0 {
+            sqlx::query(
+                r#"
+                UPDATE revolutions
+                SET total_support = total_support + $3::numeric, updated_at = NOW()
+                WHERE region_id = $1 AND epoch = $2
+                "#,
+            )
+            .bind(region_id)
+            .bind(epoch)
+            .bind(weight)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Marks the revolution executed and ousts the governor elected in the
+    /// previous epoch, who was ruling when the revolution succeeded.
+    pub async fn record_revolution_executed(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        region_id: i64,
+        epoch: i64,
+        ousted_governor: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE revolutions
+            SET executed = TRUE, ousted_governor = $3, executed_block = $4, updated_at = NOW()
+            WHERE region_id = $1 AND epoch = $2
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .bind(ousted_governor)
+        .bind(meta.block_number)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE governors
+            SET ousted = TRUE, ousted_epoch = $2, updated_at = NOW()
+            WHERE region_id = $1 AND epoch = $2 - 1
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drops everything mined after `block`, then recomputes the tallies,
+    /// support totals and ousted flags from the surviving rows.
+    pub async fn rollback_after(&self, conn: &mut PgConnection, block: i64) -> Result<()> {
+        for table in [
+            "election_votes",
+            "revolution_supporters",
+            "election_candidates",
+            "governors",
+            "revolutions",
+        ] {
+            sqlx::query(&format!("DELETE FROM {} WHERE block_number > $1", table))
+                .bind(block)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE revolutions
+            SET executed = FALSE, ousted_governor = NULL, executed_block = NULL, updated_at = NOW()
+            WHERE executed_block > $1
+            "#,
+        )
+        .bind(block)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE election_candidates c
+            SET total_votes = COALESCE(
+                (SELECT SUM(v.weight) FROM election_votes v
+                 WHERE v.region_id = c.region_id AND v.epoch = c.epoch AND v.candidate = c.candidate),
+                0
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE revolutions r
+            SET total_support = r.provocateur_power + COALESCE(
+                (SELECT SUM(s.weight) FROM revolution_supporters s
+                 WHERE s.region_id = r.region_id AND s.epoch = r.epoch),
+                0
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE governors g
+            SET ousted = r.executed IS TRUE,
+                ousted_epoch = CASE WHEN r.executed THEN r.epoch END,
+                updated_at = NOW()
+            FROM governors g2
+            LEFT JOIN revolutions r ON r.region_id = g2.region_id AND r.epoch = g2.epoch + 1
+            WHERE g.region_id = g2.region_id AND g.epoch = g2.epoch
+            "#,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Whether the wallet ever ran for governor under the guild or voted for one
+    /// of its candidates in that election. The chain records no guild roster,
+    /// so this is the closest indexed stand-in. Guild names match ignoring case.
+    pub async fn is_guild_member(&self, wallet_address: &str, guild_name: &str) -> Result<bool> {
+        let member = sqlx::query(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM election_candidates
+                WHERE candidate = $1 AND LOWER(guild_name) = LOWER($2)
+            ) OR EXISTS (
+                SELECT 1 FROM election_votes v
+                JOIN election_candidates c
+                  ON c.region_id = v.region_id AND c.epoch = v.epoch AND c.candidate = v.candidate
+                WHERE v.voter = $1 AND LOWER(c.guild_name) = LOWER($2)
+            ) AS member
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(guild_name)
+        .fetch_one(&self.pool)
+        .await?
+        .get("member");
+
+        Ok(member)
+    }
+
+    pub async fn find_candidates(&self, region_id: i64, epoch: i64) -> Result<Vec<Candidate>> {
+        let candidates = sqlx::query_as::<_, Candidate>(
+            r#"
+            SELECT region_id, epoch, candidate, guild_name, total_votes::TEXT AS total_votes
+            FROM election_candidates
+            WHERE region_id = $1 AND epoch = $2
+            ORDER BY total_votes DESC, block_number
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(candidates)
+    }
+
+    pub async fn find_election_votes(
+        &self,
+        region_id: i64,
+        epoch: i64,
+    ) -> Result<Vec<ElectionVote>> {
+        let votes = sqlx::query_as::<_, ElectionVote>(
+            r#"
+            SELECT region_id, epoch, voter, candidate, weight::TEXT AS weight, block_number, tx_hash
+            FROM election_votes
+            WHERE region_id = $1 AND epoch = $2
+            ORDER BY block_number, log_index
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(votes)
+    }
+
+    /// Governor whose term covers `epoch`, i.e. the winner of the election
+    /// held in `epoch - 1`. Still returned when ousted; callers check `ousted`.
+    pub async fn find_ruling_governor(
+        &self,
+        region_id: i64,
+        epoch: i64,
+    ) -> Result<Option<Governor>> {
+        let governor = sqlx::query_as::<_, Governor>(
+            r#"
+            SELECT region_id, epoch, governor, guild_name, votes::TEXT AS votes, ousted, ousted_epoch
+            FROM governors
+            WHERE region_id = $1 AND epoch = $2 - 1
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(governor)
+    }
+
+    pub async fn find_governors_by_region(&self, region_id: i64) -> Result<Vec<Governor>> {
+        let governors = sqlx::query_as::<_, Governor>(
+            r#"
+            SELECT region_id, epoch, governor, guild_name, votes::TEXT AS votes, ousted, ousted_epoch
+            FROM governors
+            WHERE region_id = $1
+            ORDER BY epoch DESC
+            "#,
+        )
+        .bind(region_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(governors)
+    }
+
+    pub async fn find_revolution(&self, region_id: i64, epoch: i64) -> Result<Option<Revolution>> {
+        let revolution = sqlx::query_as::<_, Revolution>(
+            r#"
+            SELECT region_id, epoch, provocateur, started_at, total_support::TEXT AS total_support,
+                   executed, ousted_governor
+            FROM revolutions
+            WHERE region_id = $1 AND epoch = $2
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(revolution)
+    }
+
+    pub async fn find_revolutions_by_region(&self, region_id: i64) -> Result<Vec<Revolution>> {
+        let revolutions = sqlx::query_as::<_, Revolution>(
+            r#"
+            SELECT region_id, epoch, provocateur, started_at, total_support::TEXT AS total_support,
+                   executed, ousted_governor
+            FROM revolutions
+            WHERE region_id = $1
+            ORDER BY epoch DESC
+            "#,
+        )
+        .bind(region_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(revolutions)
+    }
+
+    pub async fn find_revolution_supporters(
+        &self,
+        region_id: i64,
+        epoch: i64,
+    ) -> Result<Vec<RevolutionSupporter>> {
+        let supporters = sqlx::query_as::<_, RevolutionSupporter>(
+            r#"
+            SELECT region_id, epoch, supporter, weight::TEXT AS weight, block_number, tx_hash
+            FROM revolution_supporters
+            WHERE region_id = $1 AND epoch = $2
+            ORDER BY block_number, log_index
+            "#,
+        )
+        .bind(region_id)
+        .bind(epoch)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(supporters)
+    }
+}
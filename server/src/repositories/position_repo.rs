@@ -0,0 +1,52 @@
+use crate::models::position::PlayerPosition;
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub struct PositionRepository {
+    pool: PgPool,
+}
+
+impl PositionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_position(&self, wallet_address: &str) -> Result<Option<PlayerPosition>> {
+        let position = sqlx::query_as::<_, PlayerPosition>(
+            r#"
+            SELECT wallet_address, scene, x, y, anim
+            FROM player_positions
+            WHERE wallet_address = $1
+            "#,
+        )
+        .bind(wallet_address)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    pub async fn save_positions(&self, positions: &[PlayerPosition]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for position in positions {
+            sqlx::query(
+                r#"
+                INSERT INTO player_positions (wallet_address, scene, x, y, anim, updated_at)
+                VALUES ($1, $2, $3, $4, $5, NOW())
+                ON CONFLICT (wallet_address)
+                DO UPDATE SET scene = $2, x = $3, y = $4, anim = $5, updated_at = NOW()
+                "#,
+            )
+            .bind(&position.wallet_address)
+            .bind(&position.scene)
+            .bind(position.x)
+            .bind(position.y)
+            .bind(&position.anim)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
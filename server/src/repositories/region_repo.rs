@@ -0,0 +1,59 @@
+use anyhow::Result;
+use sqlx::{PgPool, Row};
+
+/// Regions are registered on RegionFarm without an event, so the known
+/// regions are the ones that show up anywhere in the indexed history.
+pub struct RegionRepository {
+    pool: PgPool,
+}
+
+impl RegionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_region_ids(&self) -> Result<Vec<i64>> {
+        let ids = sqlx::query(
+            r#"
+            SELECT pid AS region_id FROM farm_positions
+            UNION SELECT region_id FROM votes
+            UNION SELECT region_id FROM bribe_deposits
+            UNION SELECT attacker_region FROM wars
+            UNION SELECT defender_region FROM wars
+            UNION SELECT region_id FROM election_candidates
+            ORDER BY region_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| row.get("region_id"))
+        .collect();
+
+        Ok(ids)
+    }
+
+    /// Whether the wallet has a stake in the region's farm pool, has voted
+    /// for it or has run for or held its governorship.
+    pub async fn is_member(&self, wallet_address: &str, region_id: i64) -> Result<bool> {
+        let member = sqlx::query(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM farm_positions
+                WHERE wallet_address = $1 AND pid = $2 AND staked > 0
+                UNION ALL SELECT 1 FROM votes WHERE voter = $1 AND region_id = $2
+                UNION ALL SELECT 1 FROM election_candidates
+                WHERE candidate = $1 AND region_id = $2
+                UNION ALL SELECT 1 FROM governors WHERE governor = $1 AND region_id = $2
+            ) AS member
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(region_id)
+        .fetch_one(&self.pool)
+        .await?
+        .get("member");
+
+        Ok(member)
+    }
+}
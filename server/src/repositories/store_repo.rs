@@ -0,0 +1,142 @@
+use crate::models::chain::LogMeta;
+use crate::models::store::{InventoryItem, StoreItem};
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool};
+
+pub struct StoreRepository {
+    pool: PgPool,
+}
+
+impl StoreRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record_item_added(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        item_id: i64,
+        name: String,
+        price: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO store_items (item_id, name, price, block_number, tx_hash)
+            VALUES ($1, $2, $3::numeric, $4, $5)
+            ON CONFLICT (item_id) DO NOTHING
+            "#,
+        )
+        .bind(item_id)
+        .bind(name)
+        .bind(price)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Logs the purchase and adds it to the buyer's inventory.
+    pub async fn record_item_purchased(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        wallet_address: String,
+        item_id: i64,
+        price: String,
+    ) -> Result<()> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO item_purchases (wallet_address, item_id, price, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3::numeric, $4, $5, $6)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(&wallet_address)
+        .bind(item_id)
+        .bind(&price)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        if inserted > 0 {
+            sqlx::query(
+                r#"
+                INSERT INTO player_inventory (wallet_address, item_id, quantity, total_spent, updated_at)
+                VALUES ($1, $2, 1, $3::numeric, NOW())
+                ON CONFLICT (wallet_address, item_id)
+                DO UPDATE SET quantity = player_inventory.quantity + 1,
+                              total_spent = player_inventory.total_spent + $3::numeric,
+                              updated_at = NOW()
+                "#,
+            )
+            .bind(wallet_address)
+            .bind(item_id)
+            .bind(price)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Drops items and purchases after `block` and rebuilds inventories.
+    pub async fn rollback_after(&self, conn: &mut PgConnection, block: i64) -> Result<()> {
+        for table in ["item_purchases", "store_items"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE block_number > $1", table))
+                .bind(block)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        sqlx::query("DELETE FROM player_inventory")
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO player_inventory (wallet_address, item_id, quantity, total_spent)
+            SELECT wallet_address, item_id, COUNT(*), SUM(price)
+            FROM item_purchases
+            GROUP BY wallet_address, item_id
+            "#,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_items(&self) -> Result<Vec<StoreItem>> {
+        let items = sqlx::query_as::<_, StoreItem>(
+            "SELECT item_id, name, price::TEXT AS price FROM store_items ORDER BY item_id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(items)
+    }
+
+    pub async fn find_inventory(&self, wallet_address: &str) -> Result<Vec<InventoryItem>> {
+        let items = sqlx::query_as::<_, InventoryItem>(
+            r#"
+            SELECT i.item_id, COALESCE(s.name, '') AS name, i.quantity,
+                   i.total_spent::TEXT AS total_spent
+            FROM player_inventory i
+            LEFT JOIN store_items s ON s.item_id = i.item_id
+            WHERE i.wallet_address = $1 AND i.quantity > 0
+            ORDER BY i.item_id
+            "#,
+        )
+        .bind(wallet_address)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(items)
+    }
+}
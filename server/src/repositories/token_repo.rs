@@ -0,0 +1,122 @@
+use crate::models::chain::LogMeta;
+use crate::models::token::TokenBalance;
+use alloy::primitives::Address;
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool};
+
+/// ERC-20 balances of the game tokens (OLIG and mETH), built from transfers.
+pub struct TokenRepository {
+    pool: PgPool,
+}
+
+impl TokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Logs the transfer and moves the amount between the two balances. The
+    /// zero address stands for mints and burns and gets no balance.
+    pub async fn record_transfer(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        token_address: String,
+        from: String,
+        to: String,
+        amount: String,
+    ) -> Result<()> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO token_transfers
+                (token_address, from_address, to_address, amount, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4::numeric, $5, $6, $7)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(&token_address)
+        .bind(&from)
+        .bind(&to)
+        .bind(&amount)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        if inserted == 0 {
+            return Ok(());
+        }
+
+        let zero = Address::ZERO.to_string();
+        for (wallet, delta) in [(from, format!("-{}", amount)), (to, amount)] {
+            if wallet == zero {
+                continue;
+            }
+            sqlx::query(
+                r#"
+                INSERT INTO token_balances (token_address, wallet_address, balance, updated_at)
+                VALUES ($1, $2, $3::numeric, NOW())
+                ON CONFLICT (token_address, wallet_address)
+                DO UPDATE SET balance = token_balances.balance + $3::numeric, updated_at = NOW()
+                "#,
+            )
+            .bind(&token_address)
+            .bind(wallet)
+            .bind(delta)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Drops transfers after `block` and rebuilds every balance from the rest.
+    pub async fn rollback_after(&self, conn: &mut PgConnection, block: i64) -> Result<()> {
+        sqlx::query("DELETE FROM token_transfers WHERE block_number > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query("DELETE FROM token_balances")
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO token_balances (token_address, wallet_address, balance)
+            SELECT token_address, wallet_address, SUM(delta)
+            FROM (
+                SELECT token_address, to_address AS wallet_address, amount AS delta
+                FROM token_transfers
+                UNION ALL
+                SELECT token_address, from_address, -amount
+                FROM token_transfers
+            ) moves
+            WHERE wallet_address <> $1
+            GROUP BY token_address, wallet_address
+            "#,
+        )
+        .bind(Address::ZERO.to_string())
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_balance(&self, token_address: &str, wallet_address: &str) -> Result<String> {
+        let balance = sqlx::query_as::<_, TokenBalance>(
+            r#"
+            SELECT token_address, wallet_address, balance::TEXT AS balance
+            FROM token_balances
+            WHERE token_address = $1 AND wallet_address = $2
+            "#,
+        )
+        .bind(token_address)
+        .bind(wallet_address)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(balance.map_or_else(|| "0".to_string(), |balance| balance.balance))
+    }
+}
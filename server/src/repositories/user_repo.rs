@@ -1,7 +1,6 @@
-// use crate::error::AppError;
-// use crate::models::user::User;
+use crate::models::user::User;
 use anyhow::Result;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 pub struct UserRepository {
     pool: PgPool,
@@ -14,6 +13,7 @@ impl UserRepository {
 
     pub async fn create_or_update_user(
         &self,
+        conn: &mut PgConnection,
         wallet_address: String,
         balance: String,
     ) -> Result<()> {
@@ -27,18 +27,45 @@ impl UserRepository {
         )
         .bind(wallet_address)
         .bind(balance)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
         Ok(())
     }
 
-    // pub async fn find_user(&self, wallet_address: &str) -> Result<Option<User>, AppError> {
-    //     let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE wallet_address = $1")
-    //         .bind(wallet_address)
-    //         .fetch_optional(&self.pool)
-    //         .await?;
+    /// Recomputes every balance as the wallet's total farm stake, used after
+    /// farm logs were rolled back.
+    pub async fn rebuild_balances(&self, conn: &mut PgConnection) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE users u
+            SET balance = COALESCE(
+                    (SELECT SUM(CASE WHEN f.is_deposit THEN f.amount ELSE -(f.amount + f.tax) END)
+                     FROM farm_events f
+                     WHERE f.wallet_address = u.wallet_address),
+                    0
+                ),
+                updated_at = NOW()
+            "#,
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
 
-    //     Ok(user)
-    // }
+    pub async fn find_user(&self, wallet_address: &str) -> Result<Option<User>> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            SELECT wallet_address, balance::TEXT AS balance, created_at, updated_at
+            FROM users
+            WHERE wallet_address = $1
+            "#,
+        )
+        .bind(wallet_address)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
 }
@@ -0,0 +1,212 @@
+use crate::models::chain::LogMeta;
+use crate::models::ve::VeLock;
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool};
+
+pub struct VeRepository {
+    pool: PgPool,
+}
+
+impl VeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record_lock(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        wallet_address: String,
+        amount: String,
+        unlock_time: i64,
+    ) -> Result<()> {
+        if !self
+            .record_event(
+                &mut *conn,
+                meta,
+                &wallet_address,
+                true,
+                &amount,
+                unlock_time,
+            )
+            .await?
+        {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO ve_locks (wallet_address, amount, unlock_time, block_number, tx_hash, updated_at)
+            VALUES ($1, $2::numeric, $3, $4, $5, NOW())
+            ON CONFLICT (wallet_address)
+            DO UPDATE SET amount = $2::numeric, unlock_time = $3, block_number = $4, tx_hash = $5,
+                          updated_at = NOW()
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(amount)
+        .bind(unlock_time)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// `withdraw` always releases the whole lock.
+    pub async fn record_withdraw(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        wallet_address: String,
+        amount: String,
+    ) -> Result<()> {
+        if !self
+            .record_event(&mut *conn, meta, &wallet_address, false, &amount, 0)
+            .await?
+        {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE ve_locks
+            SET amount = 0, unlock_time = 0, block_number = $2, tx_hash = $3, updated_at = NOW()
+            WHERE wallet_address = $1
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Appends the raw log; returns false if it was already recorded.
+    async fn record_event(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        wallet_address: &str,
+        is_deposit: bool,
+        amount: &str,
+        unlock_time: i64,
+    ) -> Result<bool> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO ve_lock_events
+                (wallet_address, is_deposit, amount, unlock_time, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3::numeric, $4, $5, $6, $7)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(is_deposit)
+        .bind(amount)
+        .bind(unlock_time)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        Ok(inserted > 0)
+    }
+
+    /// Drops logs after `block`; locks last touched after it are restored
+    /// from the wallet's latest remaining log.
+    pub async fn rollback_after(&self, conn: &mut PgConnection, block: i64) -> Result<()> {
+        sqlx::query("DELETE FROM ve_lock_events WHERE block_number > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query("DELETE FROM ve_locks WHERE block_number > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO ve_locks (wallet_address, amount, unlock_time, block_number, tx_hash)
+            SELECT DISTINCT ON (e.wallet_address)
+                   e.wallet_address,
+                   CASE WHEN e.is_deposit THEN e.amount ELSE 0 END,
+                   CASE WHEN e.is_deposit THEN e.unlock_time ELSE 0 END,
+                   e.block_number, e.tx_hash
+            FROM ve_lock_events e
+            WHERE NOT EXISTS (SELECT 1 FROM ve_locks l WHERE l.wallet_address = e.wallet_address)
+            ORDER BY e.wallet_address, e.block_number DESC, e.log_index DESC
+            "#,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_lock(&self, wallet_address: &str) -> Result<Option<VeLock>> {
+        let lock = sqlx::query_as::<_, VeLock>(
+            r#"
+            SELECT wallet_address, amount::TEXT AS amount, unlock_time
+            FROM ve_locks
+            WHERE wallet_address = $1
+            "#,
+        )
+        .bind(wallet_address)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(lock)
+    }
+
+    /// The wallet's lock as of the log at `meta`, rebuilt from the raw events so a
+    /// `ve_locks` row that is ahead of or behind that block does not leak in.
+    pub async fn find_lock_at(
+        &self,
+        conn: &mut PgConnection,
+        wallet_address: &str,
+        meta: &LogMeta,
+    ) -> Result<Option<VeLock>> {
+        let lock = sqlx::query_as::<_, VeLock>(
+            r#"
+            SELECT wallet_address, amount::TEXT AS amount, unlock_time
+            FROM (
+                SELECT wallet_address, is_deposit, amount, unlock_time
+                FROM ve_lock_events
+                WHERE wallet_address = $1 AND (block_number, log_index) < ($2, $3)
+                ORDER BY block_number DESC, log_index DESC
+                LIMIT 1
+            ) latest
+            WHERE is_deposit
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(meta.block_number)
+        .bind(meta.log_index)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(lock)
+    }
+
+    /// Locks that still carry voting power at `timestamp`.
+    pub async fn find_active_locks(&self, timestamp: i64) -> Result<Vec<VeLock>> {
+        let locks = sqlx::query_as::<_, VeLock>(
+            r#"
+            SELECT wallet_address, amount::TEXT AS amount, unlock_time
+            FROM ve_locks
+            WHERE unlock_time > $1 AND amount > 0
+            "#,
+        )
+        .bind(timestamp)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(locks)
+    }
+}
@@ -0,0 +1,271 @@
+use crate::models::chain::LogMeta;
+use crate::models::voter::{BribeClaim, BribeDeposit, BribeSeizure, Vote};
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool, Row};
+
+pub struct VoterRepository {
+    pool: PgPool,
+}
+
+impl VoterRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record_bribe_deposit(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        epoch: i64,
+        region_id: i64,
+        depositor: String,
+        amount: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO bribe_deposits
+                (epoch, region_id, depositor, amount, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4::numeric, $5, $6, $7)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(epoch)
+        .bind(region_id)
+        .bind(depositor)
+        .bind(amount)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn record_bribe_seizure(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        epoch: i64,
+        from_region: i64,
+        to_region: i64,
+        amount: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO bribe_seizures
+                (epoch, from_region, to_region, amount, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4::numeric, $5, $6, $7)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(epoch)
+        .bind(from_region)
+        .bind(to_region)
+        .bind(amount)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn record_vote(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        epoch: i64,
+        region_id: i64,
+        voter: String,
+        weight: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO votes
+                (epoch, region_id, voter, weight, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4::numeric, $5, $6, $7)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(epoch)
+        .bind(region_id)
+        .bind(voter)
+        .bind(weight)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn record_bribe_claim(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        epoch: Option<i64>,
+        region_id: Option<i64>,
+        voter: String,
+        amount: String,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO bribe_claims
+                (epoch, region_id, voter, amount, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4::numeric, $5, $6, $7)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(epoch)
+        .bind(region_id)
+        .bind(voter)
+        .bind(amount)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn rollback_after(&self, conn: &mut PgConnection, block: i64) -> Result<()> {
+        for table in ["bribe_deposits", "bribe_seizures", "votes", "bribe_claims"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE block_number > $1", table))
+                .bind(block)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn find_bribe_deposits(
+        &self,
+        epoch: i64,
+        region_id: i64,
+    ) -> Result<Vec<BribeDeposit>> {
+        let rows = sqlx::query_as::<_, BribeDeposit>(
+            r#"
+            SELECT epoch, region_id, depositor, amount::TEXT AS amount, block_number, tx_hash, log_index, created_at
+            FROM bribe_deposits
+            WHERE epoch = $1 AND region_id = $2
+            ORDER BY block_number, log_index
+            "#,
+        )
+        .bind(epoch)
+        .bind(region_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_bribe_seizures(&self, epoch: i64) -> Result<Vec<BribeSeizure>> {
+        let rows = sqlx::query_as::<_, BribeSeizure>(
+            r#"
+            SELECT epoch, from_region, to_region, amount::TEXT AS amount, block_number, tx_hash, log_index, created_at
+            FROM bribe_seizures
+            WHERE epoch = $1
+            ORDER BY block_number, log_index
+            "#,
+        )
+        .bind(epoch)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_votes(&self, epoch: i64, region_id: i64) -> Result<Vec<Vote>> {
+        let rows = sqlx::query_as::<_, Vote>(
+            r#"
+            SELECT epoch, region_id, voter, weight::TEXT AS weight, block_number, tx_hash, log_index, created_at
+            FROM votes
+            WHERE epoch = $1 AND region_id = $2
+            ORDER BY block_number, log_index
+            "#,
+        )
+        .bind(epoch)
+        .bind(region_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_votes_by_voter(&self, voter: &str) -> Result<Vec<Vote>> {
+        let rows = sqlx::query_as::<_, Vote>(
+            r#"
+            SELECT epoch, region_id, voter, weight::TEXT AS weight, block_number, tx_hash, log_index, created_at
+            FROM votes
+            WHERE voter = $1
+            ORDER BY block_number, log_index
+            "#,
+        )
+        .bind(voter)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_bribe_claims_by_voter(&self, voter: &str) -> Result<Vec<BribeClaim>> {
+        let rows = sqlx::query_as::<_, BribeClaim>(
+            r#"
+            SELECT epoch, region_id, voter, amount::TEXT AS amount, block_number, tx_hash, log_index, created_at
+            FROM bribe_claims
+            WHERE voter = $1
+            ORDER BY block_number, log_index
+            "#,
+        )
+        .bind(voter)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Total vote weight cast for a region in an epoch (`regionData.totalVotes`).
+    pub async fn vote_weight(&self, epoch: i64, region_id: i64) -> Result<String> {
+        let weight: String = sqlx::query(
+            r#"
+            SELECT COALESCE(SUM(weight), 0)::TEXT AS weight
+            FROM votes
+            WHERE epoch = $1 AND region_id = $2
+            "#,
+        )
+        .bind(epoch)
+        .bind(region_id)
+        .fetch_one(&self.pool)
+        .await?
+        .get("weight");
+
+        Ok(weight)
+    }
+
+    /// Bribes a region holds for an epoch (`regionData.bribeAmount`): deposits
+    /// plus loot seized from other regions, minus loot seized from it. Claims
+    /// are paid out pro rata without reducing it.
+    pub async fn bribe_pot(&self, epoch: i64, region_id: i64) -> Result<String> {
+        let pot: String = sqlx::query(
+            r#"
+            SELECT (
+                (SELECT COALESCE(SUM(amount), 0) FROM bribe_deposits WHERE epoch = $1 AND region_id = $2)
+                + (SELECT COALESCE(SUM(amount), 0) FROM bribe_seizures WHERE epoch = $1 AND to_region = $2)
+                - (SELECT COALESCE(SUM(amount), 0) FROM bribe_seizures WHERE epoch = $1 AND from_region = $2)
+            )::TEXT AS pot
+            "#,
+        )
+        .bind(epoch)
+        .bind(region_id)
+        .fetch_one(&self.pool)
+        .await?
+        .get("pot");
+
+        Ok(pot)
+    }
+}
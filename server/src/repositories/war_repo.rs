@@ -0,0 +1,224 @@
+use crate::models::chain::LogMeta;
+use crate::models::war::{RegionWarRecord, War, WarContribution, WarEnlistment};
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool};
+
+pub struct WarRepository {
+    pool: PgPool,
+}
+
+impl WarRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record_war_declared(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        epoch: i64,
+        attacker_region: i64,
+        defender_region: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO wars (epoch, attacker_region, defender_region, declared_block, declared_tx_hash)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (epoch, attacker_region) DO NOTHING
+            "#,
+        )
+        .bind(epoch)
+        .bind(attacker_region)
+        .bind(defender_region)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_troops_enlisted(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        epoch: i64,
+        region_id: i64,
+        wallet_address: String,
+        amount: String,
+        is_attack: bool,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO war_enlistments
+                (epoch, region_id, wallet_address, amount, is_attack, block_number, tx_hash, log_index)
+            VALUES ($1, $2, $3, $4::numeric, $5, $6, $7, $8)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(epoch)
+        .bind(region_id)
+        .bind(wallet_address)
+        .bind(amount)
+        .bind(is_attack)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .bind(meta.log_index)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn record_war_result(
+        &self,
+        conn: &mut PgConnection,
+        meta: &LogMeta,
+        epoch: i64,
+        attacker_region: i64,
+        attacker_won: bool,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE wars
+            SET resolved = TRUE, attacker_won = $3, resolved_block = $4, resolved_tx_hash = $5,
+                updated_at = NOW()
+            WHERE epoch = $1 AND attacker_region = $2
+            "#,
+        )
+        .bind(epoch)
+        .bind(attacker_region)
+        .bind(attacker_won)
+        .bind(meta.block_number)
+        .bind(&meta.tx_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drops declarations and enlistments after `block` and reopens wars
+    /// whose result was mined after it.
+    pub async fn rollback_after(&self, conn: &mut PgConnection, block: i64) -> Result<()> {
+        sqlx::query("DELETE FROM war_enlistments WHERE block_number > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query("DELETE FROM wars WHERE declared_block > $1")
+            .bind(block)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE wars
+            SET resolved = FALSE, attacker_won = NULL, resolved_block = NULL,
+                resolved_tx_hash = NULL, updated_at = NOW()
+            WHERE resolved_block > $1
+            "#,
+        )
+        .bind(block)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_wars_by_epoch(&self, epoch: i64) -> Result<Vec<War>> {
+        let wars = sqlx::query_as::<_, War>(
+            r#"
+            SELECT epoch, attacker_region, defender_region, resolved, attacker_won,
+                   declared_block, declared_tx_hash, resolved_block, resolved_tx_hash
+            FROM wars
+            WHERE epoch = $1
+            ORDER BY declared_block, attacker_region
+            "#,
+        )
+        .bind(epoch)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(wars)
+    }
+
+    pub async fn find_wars_by_region(&self, region_id: i64) -> Result<Vec<War>> {
+        let wars = sqlx::query_as::<_, War>(
+            r#"
+            SELECT epoch, attacker_region, defender_region, resolved, attacker_won,
+                   declared_block, declared_tx_hash, resolved_block, resolved_tx_hash
+            FROM wars
+            WHERE attacker_region = $1 OR defender_region = $1
+            ORDER BY epoch DESC, declared_block DESC
+            "#,
+        )
+        .bind(region_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(wars)
+    }
+
+    pub async fn find_enlistments(&self, epoch: i64, region_id: i64) -> Result<Vec<WarEnlistment>> {
+        let enlistments = sqlx::query_as::<_, WarEnlistment>(
+            r#"
+            SELECT epoch, region_id, wallet_address, amount::TEXT AS amount, is_attack,
+                   block_number, tx_hash, log_index
+            FROM war_enlistments
+            WHERE epoch = $1 AND region_id = $2
+            ORDER BY block_number, log_index
+            "#,
+        )
+        .bind(epoch)
+        .bind(region_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(enlistments)
+    }
+
+    /// Per-war troops of one wallet. Attack troops count toward the war their
+    /// region declared; defense troops count toward every war fought against
+    /// their region that epoch, exactly like `regionDefensePower` on chain.
+    pub async fn find_contributions(&self, wallet_address: &str) -> Result<Vec<WarContribution>> {
+        let contributions = sqlx::query_as::<_, WarContribution>(
+            r#"
+            SELECT w.epoch, w.attacker_region, w.defender_region, e.is_attack,
+                   SUM(e.amount)::TEXT AS amount, w.attacker_won
+            FROM war_enlistments e
+            JOIN wars w
+              ON w.epoch = e.epoch
+             AND ((e.is_attack AND w.attacker_region = e.region_id)
+               OR (NOT e.is_attack AND w.defender_region = e.region_id))
+            WHERE e.wallet_address = $1
+            GROUP BY w.epoch, w.attacker_region, w.defender_region, e.is_attack, w.attacker_won
+            ORDER BY w.epoch DESC, w.attacker_region
+            "#,
+        )
+        .bind(wallet_address)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(contributions)
+    }
+
+    pub async fn region_record(&self, region_id: i64) -> Result<RegionWarRecord> {
+        let record = sqlx::query_as::<_, RegionWarRecord>(
+            r#"
+            SELECT $1::BIGINT AS region_id,
+                   COUNT(*) FILTER (WHERE attacker_region = $1 AND attacker_won) AS attacks_won,
+                   COUNT(*) FILTER (WHERE attacker_region = $1 AND NOT attacker_won) AS attacks_lost,
+                   COUNT(*) FILTER (WHERE defender_region = $1 AND NOT attacker_won) AS defenses_held,
+                   COUNT(*) FILTER (WHERE defender_region = $1 AND attacker_won) AS defenses_lost
+            FROM wars
+            WHERE resolved AND (attacker_region = $1 OR defender_region = $1)
+            "#,
+        )
+        .bind(region_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+}
@@ -1,15 +1,26 @@
-use crate::models::game::Player;
+use crate::config::Config;
+use crate::game::chat::ChatHub;
+use crate::game::map::Maps;
+use crate::game::rooms::Rooms;
+use crate::game::sessions::Sessions;
 use axum::extract::ws::Message;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tokio::sync::mpsc;
 
-pub type Clients = Arc<Mutex<HashMap<String, mpsc::UnboundedSender<Result<Message, axum::Error>>>>>;
-pub type Players = Arc<Mutex<HashMap<String, Player>>>;
+/// Outgoing half of a client's websocket.
+pub type ClientSender = mpsc::UnboundedSender<Result<Message, axum::Error>>;
+/// Sign-in nonces that were issued but not used yet, with their issue time.
+pub type Nonces = Arc<Mutex<HashMap<String, Instant>>>;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub clients: Clients,
-    pub players: Players,
+    pub rooms: Rooms,
+    pub sessions: Sessions,
+    pub chat: ChatHub,
     pub db: sqlx::PgPool,
+    pub nonces: Nonces,
+    pub config: Config,
+    pub maps: Arc<Maps>,
 }
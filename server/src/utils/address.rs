@@ -0,0 +1,11 @@
+use crate::error::AppError;
+use alloy::primitives::Address;
+use std::str::FromStr;
+
+/// Parses an address from a request into the checksummed form that indexed
+/// rows, sessions and sanctions are keyed by.
+pub fn checksummed(address: &str) -> Result<String, AppError> {
+    Address::from_str(address)
+        .map(|address| address.to_string())
+        .map_err(|_| AppError::BadRequest(format!("invalid address: {}", address)))
+}
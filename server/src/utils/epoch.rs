@@ -0,0 +1,16 @@
+/// Mirrors `OligarchyVoter.EPOCH_DURATION` (1 week).
+pub const EPOCH_DURATION: u64 = 7 * 24 * 60 * 60;
+
+/// Mirrors `OligarchyVoter.CONTRACT_DEPLOYED`. The constructor assigns the
+/// misspelled `CONTRAT_DEPLOYED` instead, so the contract reads this as 0.
+pub const EPOCH_ORIGIN: u64 = 0;
+
+/// Epoch number at `timestamp`, computed like `OligarchyVoter.getCurrentEpoch`.
+pub fn epoch_at(timestamp: u64) -> u64 {
+    timestamp.saturating_sub(EPOCH_ORIGIN) / EPOCH_DURATION
+}
+
+/// First timestamp of `epoch`.
+pub fn epoch_start(epoch: u64) -> u64 {
+    EPOCH_ORIGIN + epoch * EPOCH_DURATION
+}
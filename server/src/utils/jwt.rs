@@ -1,4 +1,41 @@
-// Placeholder for JWT utils
-pub fn generate_token() {
-    // TODO: Implement JWT generation
+use anyhow::Result;
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How long a session token issued after Sign-In with Ethereum stays valid.
+pub const TOKEN_TTL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Claims {
+    /// Checksummed address of the wallet that signed in.
+    pub sub: String,
+    pub iat: u64,
+    pub exp: u64,
+}
+
+pub fn generate_token(wallet: &str, secret: &str) -> Result<String> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let claims = Claims {
+        sub: wallet.to_string(),
+        iat: now,
+        exp: now + TOKEN_TTL_SECS,
+    };
+
+    Ok(encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )?)
+}
+
+/// Checks the signature and expiry of a token and returns its claims.
+pub fn verify_token(token: &str, secret: &str) -> Result<Claims> {
+    let data = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    )?;
+
+    Ok(data.claims)
 }
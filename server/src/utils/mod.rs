@@ -1 +1,4 @@
+pub mod address;
+pub mod epoch;
 pub mod jwt;
+pub mod siwe;
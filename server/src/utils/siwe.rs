@@ -0,0 +1,126 @@
+use alloy::primitives::{Address, Signature};
+use anyhow::{Context, Result, bail, ensure};
+use chrono::{DateTime, FixedOffset, Utc};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+const FIELDS: &[&str] = &[
+    "URI",
+    "Version",
+    "Chain ID",
+    "Nonce",
+    "Issued At",
+    "Expiration Time",
+    "Not Before",
+    "Request ID",
+    "Resources",
+];
+
+/// An EIP-4361 (Sign-In with Ethereum) message.
+#[derive(Debug)]
+pub struct SiweMessage {
+    pub domain: String,
+    pub address: Address,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: DateTime<FixedOffset>,
+    pub expiration_time: Option<DateTime<FixedOffset>>,
+    pub not_before: Option<DateTime<FixedOffset>>,
+}
+
+impl SiweMessage {
+    pub fn parse(message: &str) -> Result<Self> {
+        let mut lines = message.lines();
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE))
+            .context("missing sign-in preamble")?
+            .to_string();
+        let address = Address::from_str(lines.next().context("missing address")?.trim())?;
+
+        let mut statement = None;
+        let mut fields: HashMap<&str, &str> = HashMap::new();
+        for line in lines {
+            match line.split_once(':') {
+                Some((key, value)) if FIELDS.contains(&key) => {
+                    fields.insert(key, value.trim());
+                }
+                // Resource URIs are listed as "- uri" lines; nothing here uses them
+                _ if line.is_empty() || line.starts_with("- ") => {}
+                _ if fields.is_empty() && statement.is_none() => {
+                    statement = Some(line.to_string());
+                }
+                _ => bail!("unexpected line in sign-in message: {}", line),
+            }
+        }
+
+        let field = |key: &str| {
+            fields
+                .get(key)
+                .map(|value| value.to_string())
+                .with_context(|| format!("missing {} in sign-in message", key))
+        };
+        let timestamp = |key: &str| -> Result<Option<DateTime<FixedOffset>>> {
+            fields
+                .get(key)
+                .map(|value| DateTime::parse_from_rfc3339(value))
+                .transpose()
+                .with_context(|| format!("invalid {} in sign-in message", key))
+        };
+
+        Ok(Self {
+            domain,
+            address,
+            statement,
+            uri: field("URI")?,
+            version: field("Version")?,
+            chain_id: field("Chain ID")?.parse().context("invalid Chain ID")?,
+            nonce: field("Nonce")?,
+            issued_at: timestamp("Issued At")?.context("missing Issued At")?,
+            expiration_time: timestamp("Expiration Time")?,
+            not_before: timestamp("Not Before")?,
+        })
+    }
+}
+
+/// Parses a signed sign-in message and checks that it was signed by the
+/// address it names, for this server's domain and chain, and is currently
+/// valid. The nonce is left to the caller, which owns the issued nonces.
+pub fn verify(message: &str, signature: &str, domain: &str, chain_id: u64) -> Result<SiweMessage> {
+    let parsed = SiweMessage::parse(message)?;
+
+    ensure!(parsed.domain == domain, "message is for {}", parsed.domain);
+    ensure!(
+        parsed.version == "1",
+        "unsupported version {}",
+        parsed.version
+    );
+    ensure!(
+        parsed.chain_id == chain_id,
+        "message is for chain {}",
+        parsed.chain_id
+    );
+
+    let now = Utc::now();
+    if let Some(expiration_time) = parsed.expiration_time {
+        ensure!(now < expiration_time, "message expired");
+    }
+    if let Some(not_before) = parsed.not_before {
+        ensure!(now >= not_before, "message is not valid yet");
+    }
+
+    // Wallets sign the message as an EIP-191 personal message
+    let signer = Signature::from_str(signature)?.recover_address_from_msg(message)?;
+    ensure!(
+        signer == parsed.address,
+        "signature is not from {}",
+        parsed.address
+    );
+
+    Ok(parsed)
+}